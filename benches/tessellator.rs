@@ -0,0 +1,65 @@
+//! Times meshing a tight loop of small glyphs with the generator's tessellator reused between
+//! calls, against a fresh tessellator for each glyph. Neither arm hits the glyph cache: the
+//! generator's cache is cleared after every glyph, and the fresh arm meshes outlines with
+//! `build_mesh_from_outline_builder`, which has no cache.
+//!
+//! Run with `cargo bench --bench tessellator -- <font>`, or name the font in
+//! `TRIANGLYPH_BENCH_FONT`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use trianglyph::{build_mesh_from_outline_builder, Config, MeshGenerator, OutlineBuilder, Units};
+
+const TEXT: &str = ".,:;'-_=+ilI1!|";
+const ROUNDS: u32 = 2000;
+
+fn time(mut f: impl FnMut()) -> Duration {
+    // warm up, then take the best of a few runs
+    f();
+    (0..5).map(|_| {
+        let start = Instant::now();
+        f();
+        start.elapsed()
+    }).min().unwrap()
+}
+
+fn main() {
+    // cargo passes `--bench` to harnessless benches
+    let path = std::env::args().skip(1).find(|arg| !arg.starts_with("--"))
+        .or_else(|| std::env::var("TRIANGLYPH_BENCH_FONT").ok());
+    let Some(path) = path else {
+        eprintln!("no font given: pass one after `--`, or set TRIANGLYPH_BENCH_FONT");
+        return;
+    };
+    let data = std::fs::read(&path).expect("reading the font");
+    let face = ttf_parser::Face::parse(&data, 0).expect("parsing the font");
+    let glyphs: Vec<_> = TEXT.chars().filter_map(|c| face.glyph_index(c)).collect();
+
+    // in font units, so that outlines need no scale to match the generator's
+    let config = Config {
+        extrude: None,
+        units: Units::FontUnits,
+        tolerance: 1.,
+        ..Default::default()
+    };
+    let mut generator = MeshGenerator::new_with_config(&face, config.clone());
+
+    let reused = time(|| for _ in 0..ROUNDS {
+        for &glyph in &glyphs {
+            black_box(generator.generate_mesh(glyph).unwrap());
+            generator.clear_cache();
+        }
+    });
+
+    let fresh = time(|| for _ in 0..ROUNDS {
+        for &glyph in &glyphs {
+            let mut builder = OutlineBuilder::new(1., config.tolerance);
+            face.outline_glyph(glyph, &mut builder);
+            black_box(build_mesh_from_outline_builder(builder, &config).unwrap());
+        }
+    });
+
+    let n = ROUNDS * glyphs.len() as u32;
+    println!("reused tessellator: {:?} per glyph", reused / n);
+    println!("fresh tessellator:  {:?} per glyph", fresh / n);
+}
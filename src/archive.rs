@@ -0,0 +1,131 @@
+//! Zero-copy archives of glyph meshes, for baked asset pipelines.
+//!
+//! An archive starts with a 16-byte header: the magic bytes `TGLA`, a little-endian `u16`
+//! format version, and zero padding. The rkyv archive of a [MeshSet] follows, so that it stays
+//! aligned when the whole archive is.
+
+use {
+    crate::{ArchivedMesh, GlyphId, Mesh},
+    rkyv::{rancor, util::AlignedVec, Archive, Deserialize, Serialize},
+    std::{collections::BTreeMap, io},
+};
+
+const MAGIC: [u8; 4] = *b"TGLA";
+const VERSION: u16 = 1;
+const HEADER_LEN: usize = 16;
+
+/// The meshes of a set of glyphs, which can be archived with rkyv and used straight from the
+/// archive's bytes, such as a memory-mapped file, without deserializing them.
+///
+/// A set is collected from `(GlyphId, Mesh)` pairs; a glyph that appears more than once keeps
+/// its last mesh.
+#[derive(Debug, Clone, PartialEq, Default, Archive, Serialize, Deserialize)]
+pub struct MeshSet {
+    /// The glyph IDs, in ascending order.
+    glyphs: Vec<u16>,
+    /// The mesh of each glyph, in the same order.
+    meshes: Vec<Mesh>,
+}
+
+impl MeshSet {
+    /// Get the mesh of the given `glyph`, if the set has one.
+    pub fn get(&self, glyph: GlyphId) -> Option<&Mesh> {
+        let i = self.glyphs.binary_search(&glyph.0).ok()?;
+        Some(&self.meshes[i])
+    }
+
+    /// Get the glyphs in this set, with their meshes, in ascending order of glyph ID.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (GlyphId, &Mesh)> + '_ {
+        self.glyphs.iter().map(|&g| GlyphId(g)).zip(&self.meshes)
+    }
+
+    /// Archives this set, after a versioned header.
+    ///
+    /// Returns:
+    /// The bytes of the archive, or an [io::Error] if it could not be serialized.
+    pub fn to_archive(&self) -> io::Result<AlignedVec> {
+        let mut bytes = AlignedVec::new();
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.resize(HEADER_LEN, 0);
+        rkyv::api::high::to_bytes_in::<_, rancor::Error>(self, bytes).map_err(invalid)
+    }
+
+    /// Checks an archive written by [MeshSet::to_archive], and gives access to the set in it
+    /// without deserializing it.
+    ///
+    /// Arguments:
+    /// * `bytes`: The archive. It must be aligned to 16 bytes, as memory maps and [AlignedVec]s
+    ///   are.
+    ///
+    /// Returns:
+    /// The archived set, or an [io::Error] of kind [io::ErrorKind::InvalidData] if the bytes
+    /// are not a valid archive of a supported version.
+    pub fn access(bytes: &[u8]) -> io::Result<&ArchivedMeshSet> {
+        let body = check_header(bytes)?;
+        rkyv::access::<ArchivedMeshSet, rancor::Error>(body).map_err(invalid)
+    }
+
+    /// Gives access to the set in an archive written by [MeshSet::to_archive], checking only
+    /// its header, for archives that are known to be valid, such as those built with an
+    /// application.
+    ///
+    /// Arguments:
+    /// * `bytes`: The archive. It must be aligned to 16 bytes, as memory maps and [AlignedVec]s
+    ///   are.
+    ///
+    /// Returns:
+    /// The archived set, or an [io::Error] of kind [io::ErrorKind::InvalidData] if the header is
+    /// not that of a supported version.
+    ///
+    /// # Safety
+    /// The bytes after the header must be a valid archive of a [MeshSet], as
+    /// [MeshSet::to_archive] writes.
+    pub unsafe fn access_unchecked(bytes: &[u8]) -> io::Result<&ArchivedMeshSet> {
+        let body = check_header(bytes)?;
+        // SAFETY: the caller guarantees that the body is a valid archive
+        Ok(unsafe { rkyv::access_unchecked::<ArchivedMeshSet>(body) })
+    }
+}
+
+impl FromIterator<(GlyphId, Mesh)> for MeshSet {
+    fn from_iter<I: IntoIterator<Item = (GlyphId, Mesh)>>(iter: I) -> Self {
+        let sorted: BTreeMap<u16, Mesh> = iter.into_iter().map(|(g, mesh)| (g.0, mesh)).collect();
+        let (glyphs, meshes) = sorted.into_iter().unzip();
+        Self{glyphs, meshes}
+    }
+}
+
+impl ArchivedMeshSet {
+    /// Get the archived mesh of the given `glyph`, if the set has one. It can be deserialized
+    /// into a [Mesh] with `rkyv::deserialize`.
+    pub fn get(&self, glyph: GlyphId) -> Option<&ArchivedMesh> {
+        let i = self.glyphs.binary_search_by_key(&glyph.0, |g| g.to_native()).ok()?;
+        Some(&self.meshes[i])
+    }
+
+    /// Get the number of glyphs in the set.
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Whether the set has no glyphs.
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+}
+
+/// The archive after its header, if the header is valid.
+fn check_header(bytes: &[u8]) -> io::Result<&[u8]> {
+    if bytes.len() < HEADER_LEN || bytes[.. 4] != MAGIC {
+        return Err(invalid("not a trianglyph mesh archive"));
+    }
+    if u16::from_le_bytes([bytes[4], bytes[5]]) != VERSION {
+        return Err(invalid("unsupported mesh archive version"));
+    }
+    Ok(&bytes[HEADER_LEN ..])
+}
+
+fn invalid(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
@@ -0,0 +1,37 @@
+//! Conversion to Bevy meshes.
+
+use {
+    crate::Mesh,
+    bevy_mesh::{Indices, PrimitiveTopology},
+};
+
+impl From<Mesh> for bevy_mesh::Mesh {
+    /// Converts a mesh to a Bevy mesh, as re-exported by Bevy as `bevy::render::mesh::Mesh`.
+    ///
+    /// Bevy's default coordinate system and front-face winding are those of generated meshes,
+    /// so meshes should be generated with the default
+    /// [CoordinateSystem](crate::CoordinateSystem). Texture coordinates are flipped vertically,
+    /// as Bevy's start at the top of the texture. Meshes without normals share vertices
+    /// between faces with different normals, so they are given flat normals, at the cost of a
+    /// vertex per triangle corner; generate normals for smaller meshes with smooth curves.
+    /// Bevy meshes have no submeshes, so those are dropped.
+    fn from(mesh: Mesh) -> Self {
+        let has_normals = !mesh.normals.is_empty();
+        let mut bevy = Self::new(PrimitiveTopology::TriangleList, Default::default())
+            .with_inserted_attribute(Self::ATTRIBUTE_POSITION, mesh.vertices);
+        if has_normals {
+            bevy.insert_attribute(Self::ATTRIBUTE_NORMAL, mesh.normals);
+        }
+        if !mesh.uvs.is_empty() {
+            let uvs: Vec<[f32; 2]> = mesh.uvs.into_iter().map(|[u, v]| [u, 1. - v]).collect();
+            bevy.insert_attribute(Self::ATTRIBUTE_UV_0, uvs);
+        }
+        bevy.insert_indices(Indices::U32(mesh.indices));
+
+        if !has_normals {
+            bevy.duplicate_vertices();
+            bevy.compute_flat_normals();
+        }
+        bevy
+    }
+}
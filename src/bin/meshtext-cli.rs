@@ -0,0 +1,145 @@
+//! Generates a text mesh from a font and writes it as OBJ, STL or binary glTF.
+
+use {
+    std::{fs, io, process::ExitCode},
+    trianglyph::{Align, BevelConfig, Config, MeshGenerator, TextLayout, UvMapping},
+};
+
+const USAGE: &str = "\
+Usage: meshtext-cli <font> <text> <output> [options]
+
+Writes a mesh of <text>, set in the font at <font>, to <output>. The format is chosen by the
+output's extension: .obj, .stl or .glb.
+
+Options:
+  --face <index>        The face within a font collection [default: 0]
+  --depth <depth>       Extrude glyphs to this depth; without it, meshes are flat
+  --bevel <width>       Bevel the front edge of extruded glyphs
+  --bevel-segments <n>  The number of segments in the bevel [default: 1]
+  --tolerance <dist>    The largest distance from the outline to its mesh [default: 0.1]
+  --cap-height <height> Scale the mesh so that capital letters are this tall
+  --scale <factor>      Scale the finished mesh, e.g. into millimetres for printing
+  --align <side>        Align lines to the left, center or right [default: left]
+  --normals             Generate vertex normals
+  --uvs                 Generate texture coordinates spanning the text
+  --ascii               Write ASCII STL rather than binary
+  --weld <epsilon>      Weld vertices this close together before writing STL
+  --groups              Group OBJ triangles into front, back and sides
+  --help                Show this message
+";
+
+/// The parsed command line.
+struct Args {
+    font: String,
+    text: String,
+    output: String,
+    face: u32,
+    config: Config,
+    layout: TextLayout,
+    scale: Option<f32>,
+    ascii: bool,
+    weld: Option<f32>,
+    groups: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut positional = Vec::new();
+    let mut face = 0;
+    // meshes are flat unless a depth is given
+    let mut config = Config{extrude: None, ..Config::default()};
+    let mut layout = TextLayout::default();
+    let mut bevel = None;
+    let mut segments = 1;
+    let mut scale = None;
+    let mut ascii = false;
+    let mut weld = None;
+    let mut groups = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{name} needs a value"));
+        match arg.as_str() {
+            "--help" | "-h" => return Err(String::new()),
+            "--face" => face = number(&value(&arg)?)?,
+            "--depth" => config.extrude = Some(number(&value(&arg)?)?),
+            "--bevel" => bevel = Some(number(&value(&arg)?)?),
+            "--bevel-segments" => segments = number(&value(&arg)?)?,
+            "--tolerance" => config.tolerance = number(&value(&arg)?)?,
+            "--cap-height" => config.target_cap_height = Some(number(&value(&arg)?)?),
+            "--scale" => scale = Some(number(&value(&arg)?)?),
+            "--align" => layout.align = match value(&arg)?.as_str() {
+                "left" => Align::Left,
+                "center" => Align::Center,
+                "right" => Align::Right,
+                other => return Err(format!("unknown alignment {other:?}")),
+            },
+            "--normals" => config.generate_normals = true,
+            "--uvs" => config.uv_mapping = Some(UvMapping::TextBounds),
+            "--ascii" => ascii = true,
+            "--weld" => weld = Some(number(&value(&arg)?)?),
+            "--groups" => groups = true,
+            flag if flag.starts_with("--") => return Err(format!("unknown option {flag}")),
+            _ => positional.push(arg),
+        }
+    }
+    config.bevel = bevel.map(|width| BevelConfig{width, segments});
+
+    let [font, text, output] = <[String; 3]>::try_from(positional)
+        .map_err(|_| "expected a font, some text and an output path".to_string())?;
+    Ok(Args{font, text, output, face, config, layout, scale, ascii, weld, groups})
+}
+
+fn number<T: std::str::FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("{s:?} is not a valid number"))
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let data = fs::read(&args.font).map_err(|e| format!("could not read {}: {e}", args.font))?;
+    let generator = MeshGenerator::from_bytes_with_config(&data, args.face, args.config)
+        .map_err(|e| e.to_string())?;
+    let mut mesh = generator.layout_text(&args.text, &args.layout)
+        .map_err(|e| e.to_string())?
+        .mesh;
+    if let Some(factor) = args.scale { mesh.scale(factor); }
+
+    let extension = args.output.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+    let file = || fs::File::create(&args.output).map(io::BufWriter::new);
+    let written = match extension.as_deref() {
+        Some("obj") => file().and_then(|w| mesh.write_obj(w, args.groups)),
+        Some("stl") if args.ascii => file().and_then(|w| mesh.write_stl_ascii(w, args.weld)),
+        Some("stl") => file().and_then(|w| mesh.write_stl(w, args.weld)),
+        Some("glb") => mesh.export_gltf(&args.output),
+        _ => return Err(format!("unknown output format for {}", args.output)),
+    };
+    written.map_err(|e| format!("could not write {}: {e}", args.output))
+}
+
+fn main() -> ExitCode {
+    let result = parse_args(std::env::args().skip(1)).and_then(|args| run(args).map(|_| None))
+        .or_else(|e| if e.is_empty() {Ok(Some(USAGE))} else {Err(e)});
+    match result {
+        Ok(None) => ExitCode::SUCCESS,
+        Ok(Some(usage)) => {
+            print!("{usage}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("meshtext-cli: {e}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_args;
+
+    fn parse(args: &str) -> super::Args {
+        parse_args(args.split(' ').map(String::from)).unwrap()
+    }
+
+    #[test]
+    fn depth_is_optional() {
+        assert_eq!(parse("font.ttf text out.obj").config.extrude, None);
+        assert_eq!(parse("font.ttf text out.obj --depth 0.5").config.extrude, Some(0.5));
+    }
+}
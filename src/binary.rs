@@ -0,0 +1,215 @@
+//! A compact binary encoding for [Mesh]es, for engines that want a fixed on-disk layout.
+//!
+//! All values are little-endian. The layout is:
+//! * The magic bytes `TGLM`, a `u16` format version and `u16` attribute flags.
+//! * The vertex count and index count, as `u32`s.
+//! * The bounding box, as six `f32`s: the minimum point followed by the maximum point.
+//! * The vertex positions, as three `f32`s each.
+//! * The vertex normals, as three `f32`s each, if flagged.
+//! * The vertex UVs, as two `f32`s each, if flagged.
+//! * The indices, as `u32`s.
+//! * The submeshes, if flagged: their count as a `u32`, then the kind of each as a `u32` (0 for
+//!   the front cap, 1 for the rear cap, 2 for the side walls and 3 for a plate), followed by the
+//!   start and end of its index range as `u32`s.
+
+use {
+    crate::{BoundingBox, Mesh, SubmeshKind},
+    std::io::{self, Read, Write},
+};
+
+const MAGIC: [u8; 4] = *b"TGLM";
+const VERSION: u16 = 1;
+
+/// Set if a normal section follows the vertex positions.
+pub const FLAG_NORMALS: u16 = 1 << 0;
+/// Set if a UV section follows the vertex positions and normals.
+pub const FLAG_UVS: u16 = 1 << 1;
+/// Set if a submesh section follows the indices.
+pub const FLAG_SUBMESHES: u16 = 1 << 2;
+
+const KNOWN_FLAGS: u16 = FLAG_NORMALS | FLAG_UVS | FLAG_SUBMESHES;
+
+const SUBMESH_KINDS: [SubmeshKind; 4] =
+    [SubmeshKind::Front, SubmeshKind::Back, SubmeshKind::Sides, SubmeshKind::Plate];
+
+impl Mesh {
+    /// Writes this [Mesh] in the crate's binary format.
+    ///
+    /// Arguments:
+    /// * `w`: The writer the mesh is written to.
+    pub fn write_bin<W: Write>(&self, mut w: W) -> io::Result<()> {
+        if !self.normals.is_empty() && self.normals.len() != self.vertices.len() {
+            return Err(invalid("normal count does not match vertex count"));
+        }
+        if !self.uvs.is_empty() && self.uvs.len() != self.vertices.len() {
+            return Err(invalid("UV count does not match vertex count"));
+        }
+
+        let mut flags: u16 = 0;
+        if !self.normals.is_empty() { flags |= FLAG_NORMALS; }
+        if !self.uvs.is_empty() { flags |= FLAG_UVS; }
+        if !self.submeshes.is_empty() { flags |= FLAG_SUBMESHES; }
+
+        w.write_all(&MAGIC)?;
+        w.write_all(&VERSION.to_le_bytes())?;
+        w.write_all(&flags.to_le_bytes())?;
+        w.write_all(&count(self.vertices.len())?.to_le_bytes())?;
+        w.write_all(&count(self.indices.len())?.to_le_bytes())?;
+        write_f32s(&mut w, &self.bbox.mins)?;
+        write_f32s(&mut w, &self.bbox.maxs)?;
+        for v in &self.vertices { write_f32s(&mut w, v)?; }
+        for n in &self.normals { write_f32s(&mut w, n)?; }
+        for uv in &self.uvs { write_f32s(&mut w, uv)?; }
+        for i in &self.indices { w.write_all(&i.to_le_bytes())?; }
+        if !self.submeshes.is_empty() {
+            w.write_all(&count(self.submeshes.len())?.to_le_bytes())?;
+            for (kind, range) in &self.submeshes {
+                let kind = SUBMESH_KINDS.iter().position(|k| k == kind).unwrap() as u32;
+                for x in [kind, range.start, range.end] { w.write_all(&x.to_le_bytes())?; }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a [Mesh] written by [Mesh::write_bin].
+    ///
+    /// Arguments:
+    /// * `r`: The reader the mesh is read from.
+    ///
+    /// Returns:
+    /// The [Mesh], or an [io::Error] of kind [io::ErrorKind::InvalidData] if the data is not a
+    /// valid mesh of a supported version.
+    pub fn read_bin<R: Read>(mut r: R) -> io::Result<Mesh> {
+        if read_array::<4>(&mut r)? != MAGIC {
+            return Err(invalid("not a trianglyph mesh"));
+        }
+
+        let version = u16::from_le_bytes(read_array(&mut r)?);
+        if version != VERSION {
+            return Err(invalid("unsupported mesh format version"));
+        }
+
+        let flags = u16::from_le_bytes(read_array(&mut r)?);
+        if flags & !KNOWN_FLAGS != 0 {
+            return Err(invalid("unsupported mesh attributes"));
+        }
+
+        let n_vertices = u32::from_le_bytes(read_array(&mut r)?);
+        let n_indices = u32::from_le_bytes(read_array(&mut r)?);
+        let bbox = BoundingBox::new(read_f32s(&mut r)?, read_f32s(&mut r)?);
+
+        let vertices = (0..n_vertices)
+            .map(|_| read_f32s(&mut r))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let n_normals = if flags & FLAG_NORMALS != 0 {n_vertices} else {0};
+        let normals = (0..n_normals)
+            .map(|_| read_f32s(&mut r))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let n_uvs = if flags & FLAG_UVS != 0 {n_vertices} else {0};
+        let uvs = (0..n_uvs)
+            .map(|_| read_f32s(&mut r))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let indices = (0..n_indices)
+            .map(|_| {
+                let i = u32::from_le_bytes(read_array(&mut r)?);
+                if i < n_vertices {Ok(i)} else {Err(invalid("index out of range"))}
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let n_submeshes = match flags & FLAG_SUBMESHES {
+            0 => 0,
+            _ => u32::from_le_bytes(read_array(&mut r)?),
+        };
+        let submeshes = (0..n_submeshes)
+            .map(|_| {
+                let [kind, start, end] = read_u32s(&mut r)?;
+                let kind = *SUBMESH_KINDS.get(kind as usize)
+                    .ok_or_else(|| invalid("unknown submesh kind"))?;
+                if start <= end && end <= n_indices {Ok((kind, start .. end))}
+                else {Err(invalid("submesh out of range"))}
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(Mesh{bbox, indices, vertices, normals, uvs, submeshes})
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn count(n: usize) -> io::Result<u32> {
+    u32::try_from(n).map_err(|_| invalid("mesh too large"))
+}
+
+fn read_array<const N: usize>(r: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buf = [0; N];
+    r.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_u32s<const N: usize>(r: &mut impl Read) -> io::Result<[u32; N]> {
+    let mut out = [0; N];
+    for x in &mut out { *x = u32::from_le_bytes(read_array(r)?); }
+    Ok(out)
+}
+
+fn read_f32s<const N: usize>(r: &mut impl Read) -> io::Result<[f32; N]> {
+    let mut out = [0.; N];
+    for x in &mut out { *x = f32::from_le_bytes(read_array(r)?); }
+    Ok(out)
+}
+
+fn write_f32s(w: &mut impl Write, xs: &[f32]) -> io::Result<()> {
+    xs.iter().try_for_each(|x| w.write_all(&x.to_le_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_face, Config, Mesh, MeshGenerator, UvMapping};
+
+    fn glyph_mesh() -> Mesh {
+        let face = test_face();
+        let config = Config {
+            generate_normals: true,
+            uv_mapping: Some(UvMapping::GlyphBounds),
+            ..Default::default()
+        };
+        let generator = MeshGenerator::new_with_config(&face, config);
+        generator.generate_mesh(face.glyph_index('g').unwrap()).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mesh = glyph_mesh();
+        assert_eq!(mesh.submeshes.len(), 3);
+
+        let mut bytes = Vec::new();
+        mesh.write_bin(&mut bytes).unwrap();
+        assert_eq!(Mesh::read_bin(&bytes[..]).unwrap(), mesh);
+    }
+
+    #[test]
+    fn rejects_corrupt_input() {
+        let mesh = glyph_mesh();
+        let mut bytes = Vec::new();
+        mesh.write_bin(&mut bytes).unwrap();
+
+        for len in [0, 4, 12, 40, bytes.len() / 2, bytes.len() - 1] {
+            assert!(Mesh::read_bin(&bytes[.. len]).is_err(), "truncated to {len} bytes");
+        }
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(Mesh::read_bin(&bad_magic[..]).is_err());
+
+        // the first index, past the header, bbox, positions, normals and UVs
+        let first_index = 40 + mesh.vertices.len() * (12 + 12 + 8);
+        let mut bad_index = bytes.clone();
+        bad_index[first_index .. first_index + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(Mesh::read_bin(&bad_index[..]).is_err());
+    }
+}
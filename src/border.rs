@@ -0,0 +1,121 @@
+//! Meshing glyphs as an inset fill inside a border.
+
+use {
+    crate::{build_mesh, Config, FillRule, GlyphId, Mesh, MeshGenerator, Result},
+    std::ops::Range,
+};
+
+/// A glyph meshed as a fill and the border around it, as generated by
+/// [MeshGenerator::generate_bordered].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorderedMesh {
+    /// The mesh of the fill and the border. Its submeshes list the parts of the fill, then
+    /// those of the border.
+    pub mesh: Mesh,
+    /// The range of the mesh's indices holding the glyph's outline inset by the width of the
+    /// border.
+    pub fill: Range<u32>,
+    /// The range of the mesh's indices holding the ring between the glyph's outline and the
+    /// fill.
+    pub border: Range<u32>,
+}
+
+impl<'face> MeshGenerator<'face> {
+    /// Generates the given `glyph` as an inset fill and a border ring around it, in one mesh with
+    /// a range of indices for each, so that they can be given different materials.
+    ///
+    /// Together, the fill and border cover the glyph's outline. They are meshed and extruded like
+    /// glyphs; when extruded, each has walls along the edge between them, which are hidden
+    /// inside the glyph. The fill is inset in the same way as `embolden` in the [Config] thins
+    /// a glyph, so strokes narrower than twice the border's width are left without a fill. Any
+    /// `stroke` or `contour_depth` in the [Config] is ignored.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    /// * `width`: The width of the border, in mesh units.
+    ///
+    /// Returns:
+    /// A [Result] containing the [BorderedMesh] if successful, otherwise an
+    /// [Error](crate::Error).
+    pub fn generate_bordered(&self, glyph: GlyphId, width: f32) -> Result<BorderedMesh> {
+        let Some(mut outline) = self.outline(glyph, self.config.tolerance) else {
+            return Ok(Default::default());
+        };
+        outline.adjust(&self.config);
+
+        let mut fill = outline.clone();
+        fill.embolden(-width, self.config.embolden_join, self.config.miter_limit.max(1.));
+
+        // the inset contours, reversed, cut the fill out of the outline
+        let mut border = outline;
+        border.contours.extend(fill.contours.iter().map(|c| c.iter().rev().copied().collect()));
+
+        // the outlines are already flattened, so the tolerance only sets how close together
+        // the tessellator merges edges, which must not swallow the border
+        let tolerance = match width.abs() {
+            w if w > 0. => self.config.tolerance.min(0.25 * w),
+            _ => self.config.tolerance,
+        };
+        let config = Config {
+            tolerance,
+            normalize_winding: false,
+            fill_rule: FillRule::NonZero,
+            embolden: 0.,
+            slant: 0.,
+            stroke: None,
+            contour_depth: None,
+            ..self.config.clone()
+        };
+        let tess = &mut self.tessellator.borrow_mut();
+        let mut mesh = build_mesh(fill, &config, tess)?;
+        let n_fill = mesh.indices.len() as u32;
+        mesh.append_translated(&build_mesh(border, &config, tess)?, [0.; 3]);
+        Ok(BorderedMesh {
+            fill: 0 .. n_fill,
+            border: n_fill .. mesh.indices.len() as u32,
+            mesh: mesh.in_coordinates(config.coordinates),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cross, sub, test_face, Config, Mesh, MeshGenerator, SubmeshKind};
+
+    fn area(mesh: &Mesh, range: std::ops::Range<usize>) -> f32 {
+        mesh.indices[range].as_chunks::<3>().0.iter()
+            .map(|t| t.map(|i| mesh.vertices[i as usize]))
+            .map(|[a, b, c]| cross(sub(b, a), sub(c, a))[2] / 2.)
+            .sum()
+    }
+
+    #[test]
+    fn fill_and_border_share_a_mesh() {
+        let face = test_face();
+        let glyph = face.glyph_index('O').unwrap();
+
+        // flat, the fill and border together cover the glyph
+        let config = Config{extrude: None, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let plain = generator.generate_mesh(glyph).unwrap();
+        let bordered = generator.generate_bordered(glyph, 0.02).unwrap();
+        let mesh = &bordered.mesh;
+        assert_eq!(bordered.fill.start, 0);
+        assert_eq!(bordered.fill.end, bordered.border.start);
+        assert_eq!(bordered.border.end as usize, mesh.indices.len());
+        let [fill, border] = [&bordered.fill, &bordered.border]
+            .map(|r| area(mesh, r.start as usize .. r.end as usize));
+        let whole = area(&plain, 0 .. plain.indices.len());
+        assert!(fill > 0. && border > 0.);
+        assert!((fill + border - whole).abs() < 1e-3 * whole, "{fill} + {border} != {whole}");
+
+        // extruded, the submeshes list the parts of the fill, then those of the border
+        let generator = MeshGenerator::new(&face);
+        let bordered = generator.generate_bordered(glyph, 0.02).unwrap();
+        let kinds: Vec<_> = bordered.mesh.submeshes.iter().map(|(kind, _)| *kind).collect();
+        let parts = [SubmeshKind::Front, SubmeshKind::Back, SubmeshKind::Sides];
+        assert_eq!(kinds, [parts, parts].concat());
+        assert_eq!(bordered.mesh.submeshes[2].1.end, bordered.fill.end);
+    }
+}
@@ -0,0 +1,46 @@
+//! Listing the faces in font files and collections.
+
+use {
+    crate::{face_name, Error, Result},
+    ttf_parser::name_id,
+};
+
+/// A face within font data, as listed by [faces].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaceInfo {
+    /// The index of the face within the data, as taken by
+    /// [MeshGenerator::from_bytes](crate::MeshGenerator::from_bytes).
+    pub index: u32,
+    /// The face's family name, such as "DejaVu Sans", if it has a readable one.
+    pub family: Option<String>,
+    /// The face's subfamily name, such as "Bold Oblique", if it has a readable one.
+    pub subfamily: Option<String>,
+}
+
+/// Lists the faces in font data. A single font has one face, at index 0; a font collection, such
+/// as a `.ttc` file, may have several.
+///
+/// The typographic family and subfamily names are preferred where a face has them.
+///
+/// Arguments:
+/// * `data`: The font data.
+///
+/// Returns:
+/// A [Result] containing the faces if successful, otherwise an [Error] if any face can't be
+/// parsed.
+pub fn faces(data: &[u8]) -> Result<Vec<FaceInfo>> {
+    let count = ttf_parser::fonts_in_collection(data).unwrap_or(1);
+    (0 .. count)
+        .map(|index| {
+            let face = ttf_parser::Face::parse(data, index).map_err(Error::FontParse)?;
+            let name = |preferred, fallback| {
+                face_name(&face, preferred).or_else(|| face_name(&face, fallback))
+            };
+            Ok(FaceInfo {
+                index,
+                family: name(name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY),
+                subfamily: name(name_id::TYPOGRAPHIC_SUBFAMILY, name_id::SUBFAMILY),
+            })
+        })
+        .collect()
+}
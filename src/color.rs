@@ -0,0 +1,190 @@
+//! Meshing the layers of color glyphs.
+
+use {
+    crate::{GlyphId, Mesh, MeshGenerator, Result, Tag},
+    std::ops::Range,
+};
+
+/// A color glyph meshed layer by layer into a single [Mesh], as generated by
+/// [MeshGenerator::generate_color_layers].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorMesh {
+    /// The mesh of all of the layers. Its submeshes list the parts of each layer in turn.
+    pub mesh: Mesh,
+    /// The layers, from bottom to top.
+    pub layers: Vec<ColorLayer>,
+}
+
+/// One layer of a [ColorMesh].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorLayer {
+    /// The range of the mesh's indices holding the layer's triangles.
+    pub indices: Range<u32>,
+    /// The layer's color from the face's palette, as sRGB red, green, blue and alpha, or `None`
+    /// if it should be drawn in the text's foreground color.
+    pub color: Option<[u8; 4]>,
+}
+
+impl<'face> MeshGenerator<'face> {
+    /// Generates a [Mesh] of the layers of the given color `glyph`, from the face's `COLR` and
+    /// `CPAL` tables, with a range of indices and the color of each layer, so that each can be
+    /// drawn as a submesh in its own color.
+    ///
+    /// Layers are listed from bottom to top, and are coplanar: they should be drawn in order, or
+    /// offset along Z. A glyph without color layers gives a single layer of its own outline, in
+    /// the foreground color. Only version 0 of the `COLR` table, which layers plain outlines,
+    /// is supported.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    /// * `palette`: The index of the `CPAL` palette to color the layers from; 0 is the default.
+    ///
+    /// Returns:
+    /// A [Result] containing the [ColorMesh] if successful, otherwise an [Error](crate::Error).
+    pub fn generate_color_layers(&self, glyph: GlyphId, palette: u16) -> Result<ColorMesh> {
+        let raw = self.face.raw_face();
+        let layers = raw.table(Tag::from_bytes(b"COLR"))
+            .and_then(|colr| color_layers(colr, glyph))
+            .unwrap_or_else(|| vec![(glyph, FOREGROUND)]);
+        let cpal = raw.table(Tag::from_bytes(b"CPAL"));
+
+        let mut color_mesh = ColorMesh::default();
+        for (layer, entry) in layers {
+            let mesh = &mut color_mesh.mesh;
+            let start = mesh.indices.len() as u32;
+            mesh.append_translated(&self.generate_mesh(layer)?, [0.; 3]);
+            color_mesh.layers.push(ColorLayer {
+                indices: start .. mesh.indices.len() as u32,
+                color: cpal.and_then(|cpal| palette_color(cpal, palette, entry)),
+            });
+        }
+        Ok(color_mesh)
+    }
+}
+
+/// The palette entry that stands for the foreground color.
+const FOREGROUND: u16 = 0xFFFF;
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset .. offset + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset .. offset + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Reads the layers of `glyph` from a `COLR` table, as the glyph and palette entry of each, or
+/// `None` if it has none.
+fn color_layers(colr: &[u8], glyph: GlyphId) -> Option<Vec<(GlyphId, u16)>> {
+    let base_count = u16_at(colr, 2)? as usize;
+    let bases = u32_at(colr, 4)? as usize;
+    let layers = u32_at(colr, 8)? as usize;
+    let layer_count = u16_at(colr, 12)? as usize;
+
+    // base glyph records are sorted by glyph, six bytes each
+    let (mut lo, mut hi) = (0, base_count);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let record = bases + 6 * mid;
+        match u16_at(colr, record)?.cmp(&glyph.0) {
+            std::cmp::Ordering::Less    => lo = mid + 1,
+            std::cmp::Ordering::Greater => hi = mid,
+            std::cmp::Ordering::Equal   => {
+                let first = u16_at(colr, record + 2)? as usize;
+                let count = u16_at(colr, record + 4)? as usize;
+                if first + count > layer_count { return None; }
+                return (first .. first + count)
+                    .map(|i| {
+                        let layer = layers + 4 * i;
+                        Some((GlyphId(u16_at(colr, layer)?), u16_at(colr, layer + 2)?))
+                    })
+                    .collect();
+            }
+        }
+    }
+    None
+}
+
+/// Reads the color of `entry` in `palette` from a `CPAL` table, as RGBA.
+fn palette_color(cpal: &[u8], palette: u16, entry: u16) -> Option<[u8; 4]> {
+    if entry == FOREGROUND { return None; }
+
+    let entry_count = u16_at(cpal, 2)?;
+    let palette_count = u16_at(cpal, 4)?;
+    let colors = u32_at(cpal, 8)? as usize;
+    if entry >= entry_count || palette >= palette_count { return None; }
+
+    let first = u16_at(cpal, 12 + 2 * palette as usize)? as usize;
+    let record = colors + 4 * (first + entry as usize);
+    let [b, g, r, a] = cpal.get(record .. record + 4)?.try_into().ok()?;
+    Some([r, g, b, a])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_font::{put16, put32, rect, FontBuilder}, Config, GlyphId, MeshGenerator, Units,
+    };
+
+    /// A font with a color glyph of two layers, a square in the first palette entry under a
+    /// smaller square in the foreground color, and two palettes, red and blue.
+    fn color_font() -> Vec<u8> {
+        let mut font = FontBuilder::new();
+        font.glyph(Some('■'), 600, vec![rect(0, 0, 600, 600)]);
+        font.glyph(None, 600, vec![rect(0, 0, 600, 600)]);
+        font.glyph(None, 600, vec![rect(200, 200, 400, 400)]);
+
+        let mut colr = Vec::new();
+        for v in [0, 1] { put16(&mut colr, v); }
+        for v in [14, 20] { put32(&mut colr, v); }
+        for v in [2, 1, 0, 2, 2, 0, 3, 0xFFFF] { put16(&mut colr, v); }
+        font.table(b"COLR", colr);
+
+        let mut cpal = Vec::new();
+        for v in [0, 1, 2, 2] { put16(&mut cpal, v); }
+        put32(&mut cpal, 16);
+        for v in [0, 1] { put16(&mut cpal, v); }
+        cpal.extend([0, 0, 255, 255, 255, 0, 0, 255]);
+        font.table(b"CPAL", cpal);
+        font.build()
+    }
+
+    #[test]
+    fn color_layers_are_ranges_of_one_mesh() {
+        let data = color_font();
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+        let config = Config{units: Units::FontUnits, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let glyph = face.glyph_index('■').unwrap();
+
+        let color = generator.generate_color_layers(glyph, 0).unwrap();
+        let [under, over] = &color.layers[..] else { panic!("expected two layers") };
+        assert_eq!(under.color, Some([255, 0, 0, 255]));
+        assert_eq!(over.color, None);
+        assert_eq!(under.indices.start, 0);
+        assert_eq!(under.indices.end, over.indices.start);
+        assert_eq!(over.indices.end as usize, color.mesh.indices.len());
+
+        // each layer's range holds the whole of its own glyph's mesh
+        for (layer, id) in color.layers.iter().zip([2, 3]) {
+            let mesh = generator.generate_mesh(GlyphId(id)).unwrap();
+            let range = layer.indices.start as usize .. layer.indices.end as usize;
+            assert_eq!(range.len(), mesh.indices.len());
+            let positions = color.mesh.indices[range].iter()
+                .map(|&i| color.mesh.vertices[i as usize]);
+            assert!(positions.eq(mesh.indices.iter().map(|&i| mesh.vertices[i as usize])));
+        }
+
+        let blue = generator.generate_color_layers(glyph, 1).unwrap();
+        assert_eq!(blue.layers[0].color, Some([0, 0, 255, 255]));
+        assert_eq!(blue.mesh, color.mesh);
+
+        // a glyph without layers is one layer in the foreground color
+        let plain = generator.generate_color_layers(GlyphId(3), 0).unwrap();
+        assert_eq!(plain.layers.len(), 1);
+        assert_eq!(plain.layers[0].color, None);
+        assert_eq!(plain.mesh, generator.generate_mesh(GlyphId(3)).unwrap());
+    }
+}
@@ -0,0 +1,248 @@
+//! Caching glyph meshes on disk, between runs.
+
+use {
+    crate::{
+        BevelConfig, Config, Error, FaceFlags, GlyphId, Mesh, MeshGenerator, Result,
+        StrokeConfig, Units, UvSeam,
+    },
+    std::{
+        fs, io,
+        path::PathBuf,
+        sync::atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// A cache of a [MeshGenerator]'s glyph meshes in a directory, so that tools and builds that run
+/// repeatedly don't tessellate the same character sets each time.
+///
+/// Meshes are stored in the crate's [binary](crate::binary) format, in files named for the
+/// glyph and a hash of the font data, the face's variation coordinates, the generator's
+/// [Config](crate::Config) and the crate's version, so a change to any of these misses the
+/// cache rather than returning stale meshes. One directory can hold the meshes of many fonts
+/// and configs.
+pub struct DiskCache<'a, 'face> {
+    generator: &'a MeshGenerator<'face>,
+    dir: PathBuf,
+    key: u64,
+}
+
+impl<'a, 'face> DiskCache<'a, 'face> {
+    /// Creates a [DiskCache] for the meshes of `generator`, creating the directory if needed.
+    ///
+    /// This hashes the whole of the font's data, so it is best created once per generator.
+    ///
+    /// Arguments:
+    /// * `generator`: The generator to mesh glyphs with on a miss.
+    /// * `dir`: The directory that holds the cache.
+    ///
+    /// Returns:
+    /// A [Result] containing the [DiskCache] if the directory could be created, otherwise an
+    /// [Error::Io].
+    pub fn new(generator: &'a MeshGenerator<'face>, dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(Error::Io)?;
+
+        let face = generator.face().raw_face();
+        let mut hasher = Fnv1a::default();
+        hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.write(face.data);
+        for record in face.table_records {
+            hasher.write(&record.tag.to_bytes());
+            hasher.write(&record.offset.to_le_bytes());
+        }
+        for coord in generator.face().variation_coordinates() {
+            hasher.write(&coord.get().to_le_bytes());
+        }
+        hash_config(&mut hasher, &generator.config);
+        Ok(Self{generator, dir, key: hasher.0})
+    }
+
+    /// Get the mesh of `glyph` from the cache, or generate it as by
+    /// [MeshGenerator::generate_mesh] and store it in the cache.
+    ///
+    /// Entries that can't be read as meshes, such as those left by an interrupted write, are
+    /// treated as misses and replaced.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    ///
+    /// Returns:
+    /// A [Result] containing the [Mesh] if successful, otherwise an [Error], which is an
+    /// [Error::Io] if the cache could not be read or written.
+    pub fn generate_mesh(&self, glyph: GlyphId) -> Result<Mesh> {
+        let path = self.dir.join(format!("{:016x}-{}.tglm", self.key, glyph.0));
+        match fs::File::open(&path) {
+            Ok(file) => match Mesh::read_bin(io::BufReader::new(file)) {
+                Ok(mesh) => return Ok(mesh),
+                Err(e) if e.kind() == io::ErrorKind::InvalidData
+                    || e.kind() == io::ErrorKind::UnexpectedEof => { }
+                Err(e) => return Err(Error::Io(e)),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => { }
+            Err(e) => return Err(Error::Io(e)),
+        }
+
+        let mesh = self.generator.generate_mesh(glyph)?;
+
+        // write to a temporary file first, so that readers never see a partial mesh, named so
+        // that no other writer, in this process or another, can be writing the same one
+        static WRITES: AtomicU64 = AtomicU64::new(0);
+        let thread = {
+            use std::hash::{BuildHasher, RandomState};
+            RandomState::new().hash_one(std::thread::current().id())
+        };
+        let temp = path.with_extension(format!(
+            "{}-{thread:016x}-{}.tmp",
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed),
+        ));
+        let mut bytes = Vec::new();
+        mesh.write_bin(&mut bytes).map_err(Error::Io)?;
+        fs::write(&temp, bytes)
+            .and_then(|_| fs::rename(&temp, &path))
+            .map_err(Error::Io)?;
+        Ok(mesh)
+    }
+
+    /// Get the meshes of several glyphs, as by [DiskCache::generate_mesh].
+    ///
+    /// Arguments:
+    /// * `glyphs`: The glyphs to be meshed.
+    pub fn generate_meshes(&self, glyphs: &[GlyphId]) -> Result<Vec<Mesh>> {
+        glyphs.iter().map(|&glyph| self.generate_mesh(glyph)).collect()
+    }
+}
+
+/// The 64-bit FNV-1a hash, which unlike the standard library's hashers is stable between
+/// releases, as keys that outlive the process must be.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn u8(&mut self, v: u8) {
+        self.write(&[v]);
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.write(&v.to_le_bytes());
+    }
+
+    fn f32(&mut self, v: f32) {
+        self.u32(v.to_bits());
+    }
+
+    /// Writes whether `v` is present, then its value with `write` if it is.
+    fn option<T>(&mut self, v: Option<T>, write: impl FnOnce(&mut Self, T)) {
+        self.u8(v.is_some() as u8);
+        if let Some(v) = v { write(self, v); }
+    }
+}
+
+/// Hashes every field of `config`, each by value, so that a key changes with any setting that
+/// can change a mesh and doesn't depend on how a [Config] is formatted.
+fn hash_config(h: &mut Fnv1a, config: &Config) {
+    // destructured so that a new field can't be left out of the key
+    let Config {
+        tolerance, extrude, extrude_direction, taper, twist_degrees, units, target_cap_height,
+        normalize_winding, fill_rule, embolden, embolden_join, miter_limit, slant,
+        generate_normals, crease_angle, uv_mapping, uv_seam, bevel, profile, cap_triangulation,
+        stroke, contour_depth, missing_glyph, coordinates, faces,
+    } = config;
+
+    h.f32(*tolerance);
+    h.option(*extrude, Fnv1a::f32);
+    for &v in extrude_direction { h.f32(v); }
+    h.f32(*taper);
+    h.f32(*twist_degrees);
+    match *units {
+        Units::FaceHeight => h.u8(0),
+        Units::FontUnits  => h.u8(1),
+        Units::Em         => h.u8(2),
+        Units::CapHeight  => h.u8(3),
+        Units::Scale(s)   => { h.u8(4); h.f32(s); }
+    }
+    h.option(*target_cap_height, Fnv1a::f32);
+    h.u8(*normalize_winding as u8);
+    h.u8(*fill_rule as u8);
+    h.f32(*embolden);
+    h.u8(*embolden_join as u8);
+    h.f32(*miter_limit);
+    h.f32(*slant);
+    h.u8(*generate_normals as u8);
+    h.f32(*crease_angle);
+    h.option(*uv_mapping, |h, mapping| h.u8(mapping as u8));
+    match *uv_seam {
+        UvSeam::Auto => h.u8(0),
+        UvSeam::AtPoint([x, y]) => { h.u8(1); h.f32(x); h.f32(y); }
+    }
+    h.option(*bevel, |h, BevelConfig{width, segments}| { h.f32(width); h.u32(segments); });
+    h.option(profile.as_deref(), |h, profile| {
+        h.u32(profile.len() as u32);
+        for &(depth, offset) in profile { h.f32(depth); h.f32(offset); }
+    });
+    h.u8(*cap_triangulation as u8);
+    h.option(*stroke, |h, StrokeConfig{width, join}| { h.f32(width); h.u8(join as u8); });
+    h.option(contour_depth.as_deref(), |h, depths| {
+        h.u32(depths.len() as u32);
+        for &depth in depths { h.f32(depth); }
+    });
+    h.u8(*missing_glyph as u8);
+    h.u8(*coordinates as u8);
+    let FaceFlags{front, back, sides} = *faces;
+    for flag in [front, back, sides] { h.u8(flag as u8); }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::DiskCache,
+        crate::{test_face, Config, MeshGenerator},
+    };
+
+    #[test]
+    fn keys_follow_config_values() {
+        let face = test_face();
+        let dir = std::env::temp_dir().join(format!("trianglyph-test-{}", std::process::id()));
+        let key = |config: Config| {
+            let generator = MeshGenerator::new_with_config(&face, config);
+            DiskCache::new(&generator, &dir).unwrap().key
+        };
+        let profile = |offset| Config {
+            profile: Some(vec![(0., 0.), (1., offset)].into()),
+            ..Default::default()
+        };
+        let depths = |depth| Config {
+            contour_depth: Some(vec![0., depth].into()),
+            ..Default::default()
+        };
+
+        assert_eq!(key(Config::default()), key(Config::default()));
+        assert_eq!(key(profile(0.1)), key(profile(0.1)));
+        assert_ne!(key(profile(0.1)), key(profile(0.2)));
+        assert_ne!(key(depths(0.1)), key(depths(0.2)));
+        assert_ne!(key(Config::default()), key(Config{tolerance: 0.02, ..Default::default()}));
+
+        // a miss writes the mesh, which a hit reads back, leaving no temporary files behind
+        let generator = MeshGenerator::new(&face);
+        let cache = DiskCache::new(&generator, &dir).unwrap();
+        let glyph = face.glyph_index('a').unwrap();
+        let written = cache.generate_mesh(glyph).unwrap();
+        assert_eq!(cache.generate_mesh(glyph).unwrap(), written);
+        let names: Vec<_> = std::fs::read_dir(&dir).unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert!(names.iter().all(|name| name.to_string_lossy().ends_with(".tglm")));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
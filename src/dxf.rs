@@ -0,0 +1,133 @@
+//! Exporting flattened glyph outlines as DXF, for laser cutting and CNC.
+
+use {
+    crate::{nesting_levels, normalize_winding, Error, GlyphId, MeshGenerator, Result, TextLayout},
+    std::{collections::HashMap, io::Write},
+};
+
+impl<'face> MeshGenerator<'face> {
+    /// Writes the outline of `glyph` as closed DXF polylines, without tessellating it, for
+    /// laser cutters and CNC tools.
+    ///
+    /// The outline is flattened to the [Config](crate::Config)'s `tolerance` and adjusted by
+    /// its `embolden` and `slant`, in the XY plane, in mesh units; a tool that reads DXF in
+    /// millimetres may need a [Units](crate::Units) scale to match. Outer contours are wound
+    /// counter-clockwise on the layer `OUTLINE`, and holes clockwise on the layer `HOLES`.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be written.
+    /// * `w`: The writer the DXF file is written to.
+    ///
+    /// Returns:
+    /// An empty [Result] if successful, otherwise an [Error::Io].
+    pub fn write_glyph_dxf<W: Write>(&self, glyph: GlyphId, w: W) -> Result<()> {
+        self.write_dxf([(glyph, [0., 0.])], w)
+    }
+
+    /// Writes the outlines of `text`, laid out as by [layout_text](Self::layout_text), as
+    /// closed DXF polylines, as [write_glyph_dxf](Self::write_glyph_dxf) does for a glyph.
+    /// Plates, underlines and strikethroughs are left out.
+    ///
+    /// Arguments:
+    /// * `text`: The text to be written.
+    /// * `layout`: How the text is laid out.
+    /// * `w`: The writer the DXF file is written to.
+    ///
+    /// Returns:
+    /// An empty [Result] if successful, otherwise an [Error].
+    pub fn write_text_dxf<W: Write>(&self, text: &str, layout: &TextLayout, w: W) -> Result<()> {
+        let glyphs = self.layout_glyphs(text, layout)?;
+        self.write_dxf(glyphs.into_iter().map(|(_, glyph, position)| (glyph, position)), w)
+    }
+
+    fn write_dxf<W: Write>(
+        &self,
+        glyphs: impl IntoIterator<Item = (GlyphId, [f32; 2])>,
+        mut w: W,
+    ) -> Result<()> {
+        let mut outlines = HashMap::new();
+        let mut out = String::from("0\nSECTION\n2\nENTITIES\n");
+        for (glyph, [x, y]) in glyphs {
+            let outline = outlines.entry(glyph).or_insert_with(|| {
+                let mut outline = self.outline(glyph, self.config.tolerance)?;
+                outline.adjust(&self.config);
+                normalize_winding(&mut outline.contours);
+                let levels = nesting_levels(&outline.contours);
+                Some(outline.contours.into_iter().zip(levels).collect::<Vec<_>>())
+            });
+
+            for (contour, level) in outline.iter().flatten().filter(|(c, _)| c.len() > 2) {
+                let layer = if level % 2 == 0 {"OUTLINE"} else {"HOLES"};
+                // a closed polyline, whose vertices follow
+                out += &format!("0\nPOLYLINE\n8\n{layer}\n66\n1\n70\n1\n");
+                out += "10\n0.0\n20\n0.0\n30\n0.0\n";
+                for p in contour {
+                    let [px, py] = [x + p.x, y + p.y];
+                    out += &format!("0\nVERTEX\n8\n{layer}\n10\n{px:?}\n20\n{py:?}\n30\n0.0\n");
+                }
+                out += &format!("0\nSEQEND\n8\n{layer}\n");
+            }
+        }
+        out += "0\nENDSEC\n0\nEOF\n";
+        w.write_all(out.as_bytes()).map_err(Error::Io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_face, Config, MeshGenerator, TextLayout};
+
+    /// The layer and signed area of each polyline in `dxf`.
+    fn polylines(dxf: Vec<u8>) -> Vec<(String, f32)> {
+        let text = String::from_utf8(dxf).unwrap();
+        assert!(text.starts_with("0\nSECTION\n2\nENTITIES\n") && text.ends_with("0\nEOF\n"));
+
+        let lines: Vec<&str> = text.lines().collect();
+        let mut polylines = Vec::new();
+        let (mut layer, mut points) = (None, Vec::new());
+        for pair in lines.chunks(2) {
+            match *pair {
+                ["0", "POLYLINE"] => (layer, points) = (None, Vec::new()),
+                ["8", name] if layer.is_none() => layer = Some(name.to_string()),
+                ["10", x] => points.push([x.parse::<f32>().unwrap(), 0.]),
+                ["20", y] => points.last_mut().unwrap()[1] = y.parse().unwrap(),
+                ["0", "SEQEND"] => {
+                    // the polyline's own point comes before its vertices
+                    let points = &points[1 ..];
+                    let area = (0 .. points.len()).map(|i| {
+                        let ([x0, y0], [x1, y1]) = (points[i], points[(i + 1) % points.len()]);
+                        x0 * y1 - x1 * y0
+                    }).sum::<f32>() / 2.;
+                    polylines.push((layer.take().unwrap(), area));
+                }
+                _ => {}
+            }
+        }
+        polylines
+    }
+
+    #[test]
+    fn dxf_layers() {
+        let face = test_face();
+        let config = Config{tolerance: 0.005, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+
+        // outer contours counter-clockwise on one layer, holes clockwise on the other
+        for (c, outlines, holes) in [('B', 1, 2), ('o', 1, 1), ('i', 2, 0), (' ', 0, 0)] {
+            let mut dxf = Vec::new();
+            generator.write_glyph_dxf(face.glyph_index(c).unwrap(), &mut dxf).unwrap();
+            let polylines = polylines(dxf);
+            let on = |name: &str| polylines.iter().filter(|(layer, _)| layer == name).count();
+            assert_eq!((on("OUTLINE"), on("HOLES")), (outlines, holes), "{c}");
+            for (layer, area) in &polylines {
+                assert_eq!(layer == "OUTLINE", *area > 0., "{c}: {layer} wound {area}");
+            }
+        }
+
+        let mut dxf = Vec::new();
+        generator.write_text_dxf("Bo io", &TextLayout::default(), &mut dxf).unwrap();
+        let polylines = polylines(dxf);
+        let on = |name: &str| polylines.iter().filter(|(layer, _)| layer == name).count();
+        assert_eq!((on("OUTLINE"), on("HOLES")), (5, 4));
+    }
+}
@@ -0,0 +1,25 @@
+//! Borrowing mesh data as `glam` vectors.
+
+use {
+    crate::Mesh,
+    glam::{Vec2, Vec3},
+};
+
+impl Mesh {
+    /// Borrow this mesh's vertices as `glam` vectors, without copying them.
+    pub fn vertices_vec3(&self) -> &[Vec3] {
+        bytemuck::cast_slice(&self.vertices)
+    }
+
+    /// Borrow this mesh's normals as `glam` vectors, without copying them. This is empty if
+    /// normals weren't generated.
+    pub fn normals_vec3(&self) -> &[Vec3] {
+        bytemuck::cast_slice(&self.normals)
+    }
+
+    /// Borrow this mesh's texture coordinates as `glam` vectors, without copying them. This is
+    /// empty if UVs weren't generated.
+    pub fn uvs_vec2(&self) -> &[Vec2] {
+        bytemuck::cast_slice(&self.uvs)
+    }
+}
@@ -0,0 +1,176 @@
+//! Exporting meshes as binary glTF 2.0.
+
+use {
+    crate::{BoundingBox, Mesh},
+    std::{fmt::Write as _, io, path::Path},
+};
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+impl Mesh {
+    /// Encodes this mesh as a binary glTF 2.0 (`.glb`) file, with its positions, normals, UVs
+    /// and indices, to be opened in Blender or a glTF viewer.
+    ///
+    /// The file holds a single scene with a single node and mesh. glTF's axes and winding are
+    /// those of the default [CoordinateSystem](crate::CoordinateSystem), and its texture
+    /// coordinates start at the top of the texture, so UVs are flipped vertically. A mesh
+    /// without triangles gives an empty scene.
+    pub fn to_gltf(&self) -> Vec<u8> {
+        let mut bin = Vec::new();
+        let mut views = Vec::new();
+        let mut accessors = Vec::new();
+        let mut attributes = Vec::new();
+
+        // each attribute gets its own buffer view and accessor
+        let mut push = |bin: &mut Vec<u8>, data: Vec<u8>, target, accessor: String| {
+            let offset = bin.len();
+            bin.extend(data);
+            views.push(format!(
+                r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{},"target":{target}}}"#,
+                bin.len() - offset,
+            ));
+            accessors.push(format!(r#"{{"bufferView":{},{accessor}}}"#, views.len() - 1));
+            accessors.len() - 1
+        };
+
+        let mut primitive = String::new();
+        if !self.indices.is_empty() {
+            let n = self.vertices.len();
+            let BoundingBox{mins, maxs} = BoundingBox::from_points(&self.vertices);
+            let accessor = format!(
+                r#""componentType":{FLOAT},"count":{n},"type":"VEC3","min":{},"max":{}"#,
+                json_floats(&mins), json_floats(&maxs),
+            );
+            let i = push(&mut bin, floats(self.vertices.as_flattened()), ARRAY_BUFFER, accessor);
+            attributes.push(format!(r#""POSITION":{i}"#));
+
+            if !self.normals.is_empty() {
+                let accessor = format!(r#""componentType":{FLOAT},"count":{n},"type":"VEC3""#);
+                let data = floats(self.normals.as_flattened());
+                let i = push(&mut bin, data, ARRAY_BUFFER, accessor);
+                attributes.push(format!(r#""NORMAL":{i}"#));
+            }
+
+            if !self.uvs.is_empty() {
+                let accessor = format!(r#""componentType":{FLOAT},"count":{n},"type":"VEC2""#);
+                let flipped: Vec<f32> = self.uvs.iter().flat_map(|&[u, v]| [u, 1. - v]).collect();
+                let i = push(&mut bin, floats(&flipped), ARRAY_BUFFER, accessor);
+                attributes.push(format!(r#""TEXCOORD_0":{i}"#));
+            }
+
+            let accessor = format!(
+                r#""componentType":{UNSIGNED_INT},"count":{},"type":"SCALAR""#,
+                self.indices.len(),
+            );
+            let data = self.indices.iter().flat_map(|i| i.to_le_bytes()).collect();
+            let indices = push(&mut bin, data, ELEMENT_ARRAY_BUFFER, accessor);
+            write!(
+                primitive,
+                r#"{{"attributes":{{{}}},"indices":{indices},"mode":4}}"#,
+                attributes.join(","),
+            ).unwrap();
+        }
+
+        let mut json = String::from(r#"{"asset":{"version":"2.0","generator":"trianglyph"}"#);
+        if primitive.is_empty() {
+            json.push_str(r#","scene":0,"scenes":[{"nodes":[]}]"#);
+        } else {
+            write!(
+                json,
+                concat!(
+                    r#","scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{{"mesh":0}}]"#,
+                    r#","meshes":[{{"primitives":[{}]}}]"#,
+                    r#","buffers":[{{"byteLength":{}}}],"bufferViews":[{}],"accessors":[{}]"#,
+                ),
+                primitive, bin.len(), views.join(","), accessors.join(","),
+            ).unwrap();
+        }
+        json.push('}');
+
+        // chunks are padded to four bytes, JSON with spaces and binary data with zeros
+        let mut json = json.into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        bin.resize(bin.len().next_multiple_of(4), 0);
+
+        let length = 12 + 8 + json.len() + if bin.is_empty() {0} else {8 + bin.len()};
+        let mut glb = Vec::with_capacity(length);
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&(length as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend(json);
+        if !bin.is_empty() {
+            glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+            glb.extend_from_slice(b"BIN\0");
+            glb.extend(bin);
+        }
+        glb
+    }
+
+    /// Writes this mesh to a binary glTF 2.0 (`.glb`) file, as encoded by [Mesh::to_gltf].
+    ///
+    /// Arguments:
+    /// * `path`: The path of the file to be written.
+    pub fn export_gltf(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_gltf())
+    }
+}
+
+fn floats(xs: &[f32]) -> Vec<u8> {
+    xs.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn json_floats(xs: &[f32]) -> String {
+    let xs: Vec<String> = xs.iter().map(|x| format!("{x:?}")).collect();
+    format!("[{}]", xs.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_face, Config, MeshGenerator, UvMapping};
+
+    /// The JSON and binary chunks of `glb`, after checking its header.
+    fn chunks(glb: &[u8]) -> (String, &[u8]) {
+        let word = |at: usize| u32::from_le_bytes(glb[at .. at + 4].try_into().unwrap()) as usize;
+        assert_eq!((&glb[.. 4], word(4), word(8)), (&b"glTF"[..], 2, glb.len()));
+        let json_end = 20 + word(12);
+        assert_eq!(&glb[16 .. 20], b"JSON");
+        let json = String::from_utf8(glb[20 .. json_end].to_vec()).unwrap();
+        if json_end == glb.len() { return (json, &[]); }
+        assert_eq!(&glb[json_end + 4 .. json_end + 8], b"BIN\0");
+        assert_eq!(json_end + 8 + word(json_end), glb.len());
+        (json, &glb[json_end + 8 ..])
+    }
+
+    #[test]
+    fn gltf_structure() {
+        let face = test_face();
+        let config = Config {
+            generate_normals: true,
+            uv_mapping: Some(UvMapping::GlyphBounds),
+            ..Default::default()
+        };
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let mesh = generator.generate_text("Bo").unwrap();
+        let glb = mesh.to_gltf();
+        let (json, bin) = chunks(&glb);
+
+        // positions, normals and UVs for each vertex, and the indices
+        let (n, i) = (mesh.vertices.len(), mesh.indices.len());
+        assert_eq!(bin.len(), n * (12 + 12 + 8) + i * 4);
+        assert!(json.contains(&format!(r#""buffers":[{{"byteLength":{}}}]"#, bin.len())));
+        assert_eq!(json.matches(&format!(r#""count":{n},"type":"VEC3""#)).count(), 2);
+        assert_eq!(json.matches(&format!(r#""count":{n},"type":"VEC2""#)).count(), 1);
+        assert_eq!(json.matches(&format!(r#""count":{i},"type":"SCALAR""#)).count(), 1);
+
+        let space = generator.generate_text(" ").unwrap();
+        let glb = space.to_gltf();
+        let (json, bin) = chunks(&glb);
+        assert!(bin.is_empty());
+        assert!(json.contains(r#""scenes":[{"nodes":[]}]"#) && !json.contains("accessors"));
+    }
+}
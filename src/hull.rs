@@ -0,0 +1,242 @@
+//! Convex hulls of meshes, for collision and picking proxies.
+
+use crate::{add, cross, dot, length, normalize, sub, BoundingBox, Mesh};
+
+impl Mesh {
+    /// Computes the convex hull of this mesh's vertices, as a closed mesh wound the same way as
+    /// generated meshes, for a cheap physics or picking proxy.
+    ///
+    /// The hull is built incrementally, taking the points farthest from the centre first, so
+    /// that most vertices of a glyph, which lie inside the hull, are discarded quickly. It keeps
+    /// every extreme vertex of the mesh, so curved outlines give hulls with many small faces.
+    /// The hull has no normals, texture coordinates or submeshes. A flat mesh, such as
+    /// unextruded text, gives a flat convex polygon facing the same way as the mesh, and a mesh
+    /// with no area gives an empty mesh.
+    pub fn convex_hull(&self) -> Mesh {
+        let mut points = self.vertices.clone();
+        points.sort_by(|a, b| a.map(f32::to_bits).cmp(&b.map(f32::to_bits)));
+        points.dedup();
+
+        let bbox = BoundingBox::from_points(&points);
+        let eps = 1e-5 * length(bbox.size());
+        let center = bbox.center();
+        points.sort_by(|a, b| length(sub(*b, center)).total_cmp(&length(sub(*a, center))));
+
+        // the mesh's overall facing, to orient flat hulls by
+        let facing = self.indices.as_chunks::<3>().0.iter()
+            .map(|t| t.map(|i| self.vertices[i as usize]))
+            .fold([0.; 3], |sum, [a, b, c]| add(sum, cross(sub(b, a), sub(c, a))));
+
+        let triangles = match initial_simplex(&points, eps) {
+            Simplex::Degenerate => Vec::new(),
+            Simplex::Flat(normal) => {
+                let normal = if dot(normal, facing) < 0. {normal.map(|x| -x)} else {normal};
+                flat_hull(&points, normal)
+            }
+            Simplex::Solid(tetrahedron) => solid_hull(&points, tetrahedron, eps),
+        };
+
+        // keep only the points the hull uses
+        let mut remap = vec![u32::MAX; points.len()];
+        let mut vertices = Vec::new();
+        let mut indices = Vec::with_capacity(triangles.len() * 3);
+        for &i in triangles.as_flattened() {
+            if remap[i] == u32::MAX {
+                remap[i] = vertices.len() as u32;
+                vertices.push(points[i]);
+            }
+            indices.push(remap[i]);
+        }
+
+        Mesh {
+            bbox: BoundingBox::from_points(&vertices),
+            indices,
+            vertices,
+            ..Default::default()
+        }
+    }
+}
+
+enum Simplex {
+    /// The points all lie on a line, or there are fewer than three.
+    Degenerate,
+    /// The points all lie in a plane with this normal.
+    Flat([f32; 3]),
+    /// Four points spanning a tetrahedron, wound so that its faces point outward.
+    Solid([usize; 4]),
+}
+
+fn initial_simplex(points: &[[f32; 3]], eps: f32) -> Simplex {
+    let farthest = |score: &dyn Fn([f32; 3]) -> f32| (0 .. points.len())
+        .max_by(|&a, &b| score(points[a]).total_cmp(&score(points[b])));
+    let Some(a) = farthest(&|_| 0.) else { return Simplex::Degenerate };
+    let Some(b) = farthest(&|p| length(sub(p, points[a]))) else { return Simplex::Degenerate };
+    let ab = sub(points[b], points[a]);
+    let Some(c) = farthest(&|p| length(cross(ab, sub(p, points[a]))))
+        else { return Simplex::Degenerate };
+
+    let normal = cross(ab, sub(points[c], points[a]));
+    if length(normal) <= eps * length(ab) { return Simplex::Degenerate; }
+    let normal = normalize(normal);
+    let height = |p: [f32; 3]| dot(normal, sub(p, points[a]));
+    let Some(d) = farthest(&|p| height(p).abs()) else { return Simplex::Degenerate };
+    match height(points[d]) {
+        h if h.abs() <= eps => Simplex::Flat(normal),
+        // the base faces away from the apex
+        h if h > 0. => Simplex::Solid([a, c, b, d]),
+        _ => Simplex::Solid([a, b, c, d]),
+    }
+}
+
+/// The hull of points spanning a tetrahedron, as triangles wound counter-clockwise seen from
+/// outside.
+fn solid_hull(points: &[[f32; 3]], [a, b, c, d]: [usize; 4], eps: f32) -> Vec<[usize; 3]> {
+    let mut faces = vec![[a, b, c], [a, d, b], [b, d, c], [c, d, a]];
+    let outward = |[a, b, c]: [usize; 3], p: [f32; 3]| {
+        let n = normalize(cross(sub(points[b], points[a]), sub(points[c], points[a])));
+        dot(n, sub(p, points[a]))
+    };
+
+    for (i, &p) in points.iter().enumerate() {
+        if [a, b, c, d].contains(&i) { continue; }
+        let (visible, hidden): (Vec<[usize; 3]>, Vec<_>) = faces.iter()
+            .partition(|&&f| outward(f, p) > eps);
+        if visible.is_empty() { continue; }
+
+        // the edges between visible and hidden faces are joined to the new point
+        let edges: Vec<(usize, usize)> = visible.iter()
+            .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+            .collect();
+        let horizon = edges.iter().filter(|&&(u, v)| !edges.contains(&(v, u)));
+        faces = hidden.into_iter()
+            .chain(horizon.map(|&(u, v)| [u, v, i]))
+            .collect();
+    }
+    faces
+}
+
+/// The hull of points lying in the plane with `normal`, as a fan of triangles wound
+/// counter-clockwise seen from the side `normal` points to.
+fn flat_hull(points: &[[f32; 3]], normal: [f32; 3]) -> Vec<[usize; 3]> {
+    // an orthonormal basis of the plane, with u × v = normal
+    let helper = if normal[0].abs() < 0.9 {[1., 0., 0.]} else {[0., 1., 0.]};
+    let u = normalize(cross(helper, normal));
+    let v = cross(normal, u);
+    let planar = |i: usize| [dot(points[i], u), dot(points[i], v)];
+
+    // Andrew's monotone chain, giving the hull counter-clockwise
+    let mut order: Vec<usize> = (0 .. points.len()).collect();
+    order.sort_by(|&a, &b| {
+        let ([ax, ay], [bx, by]) = (planar(a), planar(b));
+        ax.total_cmp(&bx).then(ay.total_cmp(&by))
+    });
+    let turn = |o: usize, a: usize, b: usize| {
+        let ([ox, oy], [ax, ay], [bx, by]) = (planar(o), planar(a), planar(b));
+        (ax - ox) * (by - oy) - (ay - oy) * (bx - ox)
+    };
+    let mut hull: Vec<usize> = Vec::new();
+    for pass in [&order[..], &order.iter().rev().copied().collect::<Vec<_>>()[..]] {
+        let base = hull.len();
+        for &i in pass {
+            while let [.., o, a] = hull[base ..] {
+                if turn(o, a, i) > 0. { break; }
+                hull.pop();
+            }
+            hull.push(i);
+        }
+        hull.pop();
+    }
+
+    (1 .. hull.len().saturating_sub(1)).map(|k| [hull[0], hull[k], hull[k + 1]]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{cross, dot, length, sub, test_face, BoundingBox, Config, Mesh, MeshGenerator},
+        std::collections::HashMap,
+    };
+
+    /// The number of times each directed edge of `mesh` is used.
+    fn edges(mesh: &Mesh) -> HashMap<(u32, u32), u32> {
+        let mut edges = HashMap::new();
+        for &[a, b, c] in mesh.indices.as_chunks::<3>().0 {
+            for edge in [(a, b), (b, c), (c, a)] { *edges.entry(edge).or_insert(0) += 1; }
+        }
+        edges
+    }
+
+    #[test]
+    fn solid_hull_encloses_glyph() {
+        let face = test_face();
+        let config = Config{tolerance: 0.005, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        // non-convex glyphs, one with overlapping contours
+        for glyph in ['g', '&', 'L'] {
+            let mesh = generator.generate_mesh(face.glyph_index(glyph).unwrap()).unwrap();
+            let hull = mesh.convex_hull();
+            assert!(hull.vertices.len() >= 4 && hull.vertices.len() < mesh.vertices.len());
+            assert!(hull.indices.iter().all(|&i| (i as usize) < hull.vertices.len()));
+            assert_eq!(hull.bbox, BoundingBox::from_points(&mesh.vertices));
+
+            // closed: each edge runs once either way, between two triangles wound alike
+            let edges = edges(&hull);
+            for (&(a, b), &n) in &edges {
+                assert_eq!((n, edges.get(&(b, a))), (1, Some(&1)), "{glyph}: edge {a}-{b}");
+            }
+
+            let n = hull.vertices.len() as f32;
+            let center = hull.vertices.iter()
+                .fold([0.; 3], |sum, p| std::array::from_fn(|k| sum[k] + p[k] / n));
+            for t in hull.indices.as_chunks::<3>().0 {
+                let [a, b, c] = t.map(|i| hull.vertices[i as usize]);
+                let normal = cross(sub(b, a), sub(c, a));
+                let normal = normal.map(|x| x / length(normal));
+                // wound outward, with every vertex of the glyph on or behind it
+                assert!(dot(normal, sub(a, center)) > 0., "{glyph}: {t:?} faces inward");
+                for &p in &mesh.vertices {
+                    assert!(dot(normal, sub(p, a)) <= 1e-4, "{glyph}: {p:?} outside {t:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn flat_hull_encloses_glyph() {
+        let face = test_face();
+        let config = Config{tolerance: 0.005, extrude: None, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        for glyph in ['g', '&', 'L'] {
+            let mesh = generator.generate_mesh(face.glyph_index(glyph).unwrap()).unwrap();
+            let hull = mesh.convex_hull();
+            assert!(hull.vertices.len() >= 3 && hull.vertices.len() < mesh.vertices.len());
+            assert_eq!(hull.indices.len() / 3, hull.vertices.len() - 2);
+            assert_eq!(hull.bbox, BoundingBox::from_points(&mesh.vertices));
+
+            // a fan facing the same way as the glyph
+            for t in hull.indices.as_chunks::<3>().0 {
+                let [a, b, c] = t.map(|i| hull.vertices[i as usize]);
+                let normal = cross(sub(b, a), sub(c, a));
+                assert!(normal[0] == 0. && normal[1] == 0. && normal[2] > 0., "{glyph}: {t:?}");
+            }
+
+            // with every vertex of the glyph on or left of each edge around the outline
+            let edges = edges(&hull);
+            let outline = edges.keys().filter(|&&(a, b)| !edges.contains_key(&(b, a)));
+            for &(a, b) in outline {
+                let (a, b) = (hull.vertices[a as usize], hull.vertices[b as usize]);
+                for &p in &mesh.vertices {
+                    assert!(cross(sub(b, a), sub(p, a))[2] >= -1e-6, "{glyph}: {p:?} outside");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_hull_is_empty() {
+        let face = test_face();
+        let generator = MeshGenerator::new(&face);
+        let space = generator.generate_mesh(face.glyph_index(' ').unwrap()).unwrap();
+        assert!(space.convex_hull().indices.is_empty());
+    }
+}
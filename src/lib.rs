@@ -1,9 +1,49 @@
 //! Generate triangle meshes from font glyphs.
 
-#![feature(array_chunks)]
+#[cfg(feature = "rkyv")]
+mod archive;
+pub mod binary;
+#[cfg(feature = "bevy")]
+mod bevy;
+mod collection;
+mod border;
+mod color;
+mod disk_cache;
+mod dxf;
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "gltf")]
+mod gltf;
+mod hull;
+mod lod;
+#[cfg(feature = "mint")]
+mod mint;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+mod obj;
+mod owned;
+#[cfg(feature = "parry")]
+mod parry;
+mod ply;
+mod simplify;
+mod stl;
+#[cfg(feature = "svg")]
+mod svg;
+mod svg_export;
+#[cfg(test)]
+mod test_font;
+mod text;
+mod variation;
+mod vertex;
+mod vertex_cache;
+mod weld;
+#[cfg(feature = "wgpu")]
+mod wgpu;
 
 /// A bounding box for a mesh. If the mesh is flat, the z-coordinates will be zero.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct BoundingBox {
     /// The coordinates of the minimum point.
     pub mins: [f32; 3],
@@ -23,6 +63,94 @@ impl BoundingBox {
     pub fn new(mins: [f32; 3], maxs: [f32; 3]) -> Self {
         Self{mins, maxs}
     }
+
+    /// Creates the smallest [BoundingBox] containing all of the given points.
+    ///
+    /// Arguments:
+    /// * `points`: The points to be contained.
+    ///
+    /// Returns:
+    /// The new [BoundingBox], or an empty box at the origin if there are no points.
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a [f32; 3]>) -> Self {
+        let mut points = points.into_iter();
+        let Some(&first) = points.next() else { return Self::default() };
+        points.fold(Self::new(first, first), |bbox, p| Self::new(
+            std::array::from_fn(|i| bbox.mins[i].min(p[i])),
+            std::array::from_fn(|i| bbox.maxs[i].max(p[i])),
+        ))
+    }
+
+    /// Get the smallest [BoundingBox] containing both this box and `other`.
+    ///
+    /// Arguments:
+    /// * `other`: The other box.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(
+            std::array::from_fn(|i| self.mins[i].min(other.mins[i])),
+            std::array::from_fn(|i| self.maxs[i].max(other.maxs[i])),
+        )
+    }
+
+    /// Whether `point` lies inside this box or on its surface.
+    ///
+    /// Arguments:
+    /// * `point`: The point to be tested.
+    pub fn contains_point(&self, point: [f32; 3]) -> bool {
+        (0 .. 3).all(|i| self.mins[i] <= point[i] && point[i] <= self.maxs[i])
+    }
+
+    /// Get the eight corners of this box. Bit 0 of a corner's index selects the maximum x
+    /// coordinate over the minimum, bit 1 the maximum y, and bit 2 the maximum z.
+    pub fn corners(&self) -> [[f32; 3]; 8] {
+        std::array::from_fn(|corner| {
+            std::array::from_fn(|i| if corner >> i & 1 == 0 {self.mins[i]} else {self.maxs[i]})
+        })
+    }
+
+    /// Get the point at the centre of this box.
+    pub fn center(&self) -> [f32; 3] {
+        std::array::from_fn(|i| (self.mins[i] + self.maxs[i]) / 2.)
+    }
+
+    /// Get the extent of this box along each axis.
+    pub fn size(&self) -> [f32; 3] {
+        sub(self.maxs, self.mins)
+    }
+
+    /// Get the smallest [BoundingBox] containing this box after an affine transform, such as
+    /// one from [InstancedText].
+    ///
+    /// Arguments:
+    /// * `matrix`: The transform, as a column-major 4x4 matrix. Its bottom row is ignored.
+    pub fn transformed(&self, matrix: [[f32; 4]; 4]) -> Self {
+        let corners = self.corners().map(|p| std::array::from_fn(|row| {
+            (0 .. 3).map(|col| matrix[col][row] * p[col]).sum::<f32>() + matrix[3][row]
+        }));
+        Self::from_points(&corners)
+    }
+
+    fn translated(&self, offset: [f32; 3]) -> Self {
+        Self::new(add(self.mins, offset), add(self.maxs, offset))
+    }
+
+    /// This box with its corners moved into the coordinate `system`.
+    fn in_coordinates(&self, system: CoordinateSystem) -> Self {
+        let [a, b] = [self.mins, self.maxs].map(|p| system.map(p));
+        Self::new(
+            std::array::from_fn(|i| a[i].min(b[i])),
+            std::array::from_fn(|i| a[i].max(b[i])),
+        )
+    }
+}
+
+/// A sphere enclosing a mesh, as computed by [Mesh::bounding_sphere].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundingSphere {
+    /// The centre of the sphere.
+    pub center: [f32; 3],
+    /// The radius of the sphere.
+    pub radius: f32,
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -31,6 +159,13 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug)]
 pub enum Error {
     Tessellation(lt::TessellationError),
+    FontParse(ttf_parser::FaceParsingError),
+    UnknownVariationAxis(Tag),
+    UnknownNamedInstance,
+    MissingGlyph(char),
+    TooManyVertices(usize),
+    InvalidExtrudeDirection([f32; 3]),
+    Io(std::io::Error),
 }
 
 impl std::error::Error for Error { }
@@ -40,6 +175,20 @@ impl std::fmt::Display for Error {
         match self {
             Error::Tessellation(e)
                 => write!(f, "The glyph outline could not be tesselated: {e}"),
+            Error::FontParse(e)
+                => write!(f, "The font could not be parsed: {e}"),
+            Error::UnknownVariationAxis(tag)
+                => write!(f, "The face has no variation axis {tag}"),
+            Error::UnknownNamedInstance
+                => write!(f, "The face has no such named instance"),
+            Error::MissingGlyph(c)
+                => write!(f, "The face has no glyph for {c:?}"),
+            Error::TooManyVertices(n)
+                => write!(f, "The mesh has {n} vertices, too many for 16-bit indices"),
+            Error::InvalidExtrudeDirection(d)
+                => write!(f, "The extrusion direction {d:?} does not point towards -Z"),
+            Error::Io(e)
+                => write!(f, "An I/O error occurred: {e}"),
         }
     }
 }
@@ -47,7 +196,9 @@ impl std::fmt::Display for Error {
 /// Holds the generated mesh data for the given glyph.
 ///
 /// The triangles use indexed vertices.
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Mesh {
     /// The bounding box of this mesh.
     pub bbox: BoundingBox,
@@ -57,158 +208,2406 @@ pub struct Mesh {
 
     /// The vertices of this mesh.
     pub vertices: Vec<[f32; 3]>,
+
+    /// The vertex normals of this mesh, if they were generated. This is either empty or the same
+    /// length as `vertices`.
+    pub normals: Vec<[f32; 3]>,
+
+    /// The texture coordinates of this mesh, if they were generated. This is either empty or the
+    /// same length as `vertices`.
+    pub uvs: Vec<[f32; 2]>,
+
+    /// The ranges of `indices` holding the front cap, rear cap and side walls, so that each can
+    /// be given its own material. Meshes combined from several glyphs, such as text, list the
+    /// parts of each glyph in turn, followed by any plate behind them. This is empty for meshes
+    /// that aren't made up of these parts, such as those of a [SplitMesh].
+    pub submeshes: Vec<(SubmeshKind, std::ops::Range<u32>)>,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct Config {
-    pub tolerance: f32,
-    pub extrude: bool,
+/// The part of a glyph mesh that a range of its indices holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub enum SubmeshKind {
+    /// The front cap, facing +Z.
+    Front,
+    /// The rear cap, facing -Z.
+    Back,
+    /// The side walls joining the caps, including any bevel.
+    Sides,
+    /// The whole of a [plate](TextLayout::plate) behind laid out text.
+    Plate,
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            tolerance: lt::FillOptions::DEFAULT_TOLERANCE,
-            extrude: true,
-        }
+impl Mesh {
+    /// Scales this mesh's vertices and bounding box uniformly about the origin.
+    ///
+    /// Arguments:
+    /// * `factor`: The scale factor. Must be positive, so that normals stay valid.
+    pub fn scale(&mut self, factor: f32) {
+        for v in &mut self.vertices { *v = v.map(|x| x * factor); }
+        let BoundingBox{mins, maxs} = self.bbox;
+        self.bbox = BoundingBox::new(mins.map(|x| x * factor), maxs.map(|x| x * factor));
+    }
+
+    /// Returns a copy of this mesh scaled uniformly about the origin.
+    ///
+    /// Arguments:
+    /// * `factor`: The scale factor. Must be positive, so that normals stay valid.
+    pub fn scaled(&self, factor: f32) -> Mesh {
+        let mut mesh = self.clone();
+        mesh.scale(factor);
+        mesh
     }
-}
 
-pub type FaceRef<'f> = &'f ttf_parser::Face<'f>;
-pub use ttf_parser::GlyphId;
+    /// Transforms this mesh's vertices and normals by an affine transform, and recomputes its
+    /// bounding box. Transforms that mirror the mesh also reverse the winding of its triangles,
+    /// so that they keep facing outwards.
+    ///
+    /// Arguments:
+    /// * `matrix`: The transform, as a column-major 4x4 matrix. Its bottom row is ignored.
+    pub fn transform(&mut self, matrix: [[f32; 4]; 4]) {
+        let [a, b, c, t] = matrix.map(|col| [col[0], col[1], col[2]]);
+        for v in &mut self.vertices {
+            *v = add(add(add(a.map(|x| x * v[0]), b.map(|x| x * v[1])), c.map(|x| x * v[2])), t);
+        }
 
-/// Generates glyph meshes for a font.
-pub struct MeshGenerator<'face> {
-    face: FaceRef<'face>,
-    config: Config,
-}
+        // normals go by the inverse transpose, whose columns are the cofactors over the
+        // determinant; only the determinant's sign matters once they're normalized
+        let det = dot(a, cross(b, c));
+        let sign = if det < 0. {-1.} else {1.};
+        let cofactors = [cross(b, c), cross(c, a), cross(a, b)].map(|col| col.map(|x| x * sign));
+        for n in &mut self.normals {
+            let [x, y, z] = cofactors;
+            *n = normalize(add(add(x.map(|e| e * n[0]), y.map(|e| e * n[1])), z.map(|e| e * n[2])));
+        }
 
-use lyon_tessellation::{self as lt, path as ltp, path::builder as ltpb};
+        if det < 0. {
+            for tri in self.indices.as_chunks_mut::<3>().0 { tri.swap(1, 2); }
+        }
+        self.bbox = BoundingBox::from_points(&self.vertices);
+    }
 
-impl<'face> MeshGenerator<'face> {
-    /// Creates a new [MeshGenerator].
+    /// Returns a copy of this mesh transformed by an affine transform, as by [Mesh::transform].
     ///
     /// Arguments:
-    /// * `font`: The font that will be used for rasterizing.
-    pub fn new(face: FaceRef<'face>) -> Self {
-        Self::new_with_config(face, Config::default())
+    /// * `matrix`: The transform, as a column-major 4x4 matrix. Its bottom row is ignored.
+    pub fn transformed(&self, matrix: [[f32; 4]; 4]) -> Mesh {
+        let mut mesh = self.clone();
+        mesh.transform(matrix);
+        mesh
     }
 
-    /// Creates a new [MeshGenerator] with custom quality settings.
+    /// Returns a copy of this mesh, generated by `generator`, in points at the given point size.
+    ///
+    /// See [MeshGenerator::point_size_scale] for how the units relate.
     ///
     /// Arguments:
-    /// * `font`: The font that will be used for rasterizing.
-    /// * `quality`: The [QualitySettings] that should be used.
-    pub fn new_with_config(face: FaceRef<'face>, config: Config) -> Self {
-        Self{face, config}
+    /// * `generator`: The [MeshGenerator] this mesh was generated with.
+    /// * `pt`: The point size.
+    pub fn to_point_size(&self, generator: &MeshGenerator<'_>, pt: f32) -> Mesh {
+        self.scaled(generator.point_size_scale(pt))
     }
 
-    /// Get the face used by this [MeshGenerator].
-    pub fn face(&self) -> FaceRef<'face> {
-        self.face
+    /// Converts this mesh, generated by `generator`, to points at the given point size, in place.
+    ///
+    /// See [MeshGenerator::point_size_scale] for how the units relate.
+    ///
+    /// Arguments:
+    /// * `generator`: The [MeshGenerator] this mesh was generated with.
+    /// * `pt`: The point size.
+    pub fn to_point_size_in_place(&mut self, generator: &MeshGenerator<'_>, pt: f32) {
+        self.scale(generator.point_size_scale(pt));
     }
 
-    /// Generates a new [Mesh] from the loaded font and the given `glyph`.
+    /// Computes the centroid of this mesh's surface, weighting each triangle by its area.
     ///
-    /// Arguments:
-    /// * `glyph`: The glyph to be meshed.
-    /// * `flat`: Wether the character should be laid out in a 2D mesh.
+    /// Unlike the center of the bounding box, this follows where the glyph's ink actually is. For
+    /// a mesh without any area, the center of the bounding box is returned instead.
+    pub fn centroid(&self) -> [f32; 3] {
+        surface_centroid(&self.vertices, &self.indices).unwrap_or_else(|| self.bbox.center())
+    }
+
+    /// Computes a sphere enclosing all of this mesh's vertices, for culling, using Ritter's
+    /// algorithm. The sphere isn't the smallest possible, but is usually within a few percent of
+    /// it. An empty mesh gives a sphere of radius zero at the origin.
+    pub fn bounding_sphere(&self) -> BoundingSphere {
+        let Some(&first) = self.vertices.first() else { return BoundingSphere::default() };
+        let farthest_from = |from: [f32; 3]| *self.vertices.iter()
+            .max_by(|a, b| length(sub(**a, from)).total_cmp(&length(sub(**b, from))))
+            .unwrap();
+
+        // start from the ends of a long chord, then grow to take in any points left outside
+        let a = farthest_from(first);
+        let b = farthest_from(a);
+        let mut center: [f32; 3] = std::array::from_fn(|i| (a[i] + b[i]) / 2.);
+        let mut radius = length(sub(b, a)) / 2.;
+        for &v in &self.vertices {
+            let d = length(sub(v, center));
+            if d > radius {
+                let grown = (radius + d) / 2.;
+                let shift = (grown - radius) / d;
+                center = std::array::from_fn(|i| center[i] + (v[i] - center[i]) * shift);
+                radius = grown;
+            }
+        }
+
+        // cover points that rounding leaves just outside
+        BoundingSphere{center, radius: radius * (1. + 4. * f32::EPSILON)}
+    }
+
+    /// Converts this mesh's indices to 16 bits, halving the size of its index buffer, for targets
+    /// such as WebGL and mobile GPUs where that matters. A single glyph rarely comes close to
+    /// the limit of 65536 vertices, but long runs of text can exceed it.
     ///
     /// Returns:
-    /// A [Result] containing the [Mesh] if successful, otherwise an [Error].
-    pub fn generate_mesh(&self, glyph: GlyphId) -> Result<Mesh> {
-        let scale = 1. / self.face.height() as f32;
-
-        let path_builder = ltpb::NoAttributes::wrap(ltp::path::BuilderImpl::new())
-            .flattened(self.config.tolerance)
-            .transformed(lt::geom::Scale::new(scale));
-        let mut bridge = Bridge(path_builder);
-        let Some(bbox) = self.face.outline_glyph(glyph, &mut bridge) else {
-            return Ok(Mesh::default());
+    /// A [Result] containing the indices, or [Error::TooManyVertices] if some vertex can't be
+    /// addressed with 16 bits.
+    pub fn indices_u16(&self) -> Result<Vec<u16>> {
+        self.indices.iter()
+            .map(|&i| u16::try_from(i).map_err(|_| Error::TooManyVertices(self.vertices.len())))
+            .collect()
+    }
+
+    /// Builds an index buffer with adjacency, for use with `GL_TRIANGLES_ADJACENCY` and similar
+    /// primitive types in geometry shaders.
+    ///
+    /// Each triangle `[a, b, c]` becomes six indices `[a, ab, b, bc, c, ca]`, where `ab` is the
+    /// vertex opposite the edge `a`-`b` in the neighbouring triangle that shares that edge, and so
+    /// on. Edges are matched by vertex position, so triangles that don't share vertex indices are
+    /// still treated as adjacent. At a boundary edge, which has no neighbour, the triangle's own
+    /// opposite vertex is repeated instead (`c` for the edge `a`-`b`); the "neighbour" then faces
+    /// the other way, so boundary edges always register as silhouette edges.
+    pub fn to_adjacency_indices(&self) -> Vec<u32> {
+        use std::collections::HashMap;
+
+        let mut canonical = HashMap::new();
+        let ids: Vec<u32> = self.vertices.iter().enumerate()
+            .map(|(i, v)| *canonical.entry(v.map(f32::to_bits)).or_insert(i as u32))
+            .collect();
+
+        let tris = self.indices.as_chunks::<3>().0;
+        let edge_key = |a: u32, b: u32| {
+            let (a, b) = (ids[a as usize], ids[b as usize]);
+            if a < b {(a, b)} else {(b, a)}
         };
 
-        let z = if self.config.extrude {0.5} else {0.0};
-        let bbox = BoundingBox::new(
-            [bbox.x_min as f32 * scale, bbox.y_min as f32 * scale, -z],
-            [bbox.x_max as f32 * scale, bbox.y_max as f32 * scale,  z],
-        );
+        let mut edges: HashMap<(u32, u32), Vec<(usize, u32)>> = HashMap::new();
+        for (t, &[a, b, c]) in tris.iter().enumerate() {
+            for (a, b, opposite) in [(a, b, c), (b, c, a), (c, a, b)] {
+                edges.entry(edge_key(a, b)).or_default().push((t, opposite));
+            }
+        }
+
+        tris.iter().enumerate()
+            .flat_map(|(t, &[a, b, c])| {
+                let adjacent = |a, b, own| edges[&edge_key(a, b)].iter()
+                    .find(|&&(u, _)| u != t)
+                    .map_or(own, |&(_, opposite)| opposite);
+                [a, adjacent(a, b, c), b, adjacent(b, c, a), c, adjacent(c, a, b)]
+            })
+            .collect()
+    }
+
+    /// Converts this mesh's triangle list into triangle strips, for GPUs and formats that prefer
+    /// them.
+    ///
+    /// Strips are separated by the primitive restart index `u32::MAX`, as used with
+    /// `GL_PRIMITIVE_RESTART_FIXED_INDEX` and by Vulkan, Metal and Direct3D. Within a strip, every
+    /// other triangle has its first two vertices swapped, following the usual convention, so the
+    /// winding of each triangle is kept. Strips are grown greedily across edges shared by vertex
+    /// index, so side walls strip well, while the irregular triangles of caps give shorter strips.
+    /// Triangles of different [submeshes](Mesh::submeshes) may share a strip.
+    pub fn to_triangle_strips(&self) -> Vec<u32> {
+        use std::collections::HashMap;
+
+        let tris = self.indices.as_chunks::<3>().0;
+        let mut edges: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+        for (t, &[a, b, c]) in tris.iter().enumerate() {
+            for edge in [(a, b), (b, c), (c, a)] { edges.entry(edge).or_default().push(t); }
+        }
 
-        let mut bufs = lt::VertexBuffers::<[f32; 3], u32>::new();
+        let mut used = vec![false; tris.len()];
+        let unused_across = |used: &[bool], from: u32, to: u32| -> Option<usize> {
+            edges.get(&(from, to))?.iter().copied().find(|&t| !used[t])
+        };
 
-        let v_base = bufs.vertices.len() as u32;
-        let i_base = bufs.vertices.len() as u32;
+        let mut strips = Vec::with_capacity(self.indices.len() * 2);
+        for start in 0 .. tris.len() {
+            if used[start] { continue; }
+            used[start] = true;
 
-        let path = bridge.0.build();
-        let mut tess = lt::FillTessellator::new();
-        let opts = lt::FillOptions::default()
-            .with_fill_rule(lt::FillRule::NonZero)
-            .with_tolerance(self.config.tolerance);
+            // begin with the edge that leads on to another triangle, if any does
+            let [a, b, c] = tris[start];
+            let [a, b, c] = [[a, b, c], [b, c, a], [c, a, b]].into_iter()
+                .find(|&[_, b, c]| unused_across(&used, c, b).is_some())
+                .unwrap_or([a, b, c]);
 
-        let mut buf_builder = lt::BuffersBuilder::new(
-            &mut bufs,
-            |v: lt::FillVertex<'_>| -> [f32; 3] {
-                let [x, y]: [f32; 2] = v.position().into();
-                [x, y, z]
+            if !strips.is_empty() { strips.push(u32::MAX); }
+            let strip_start = strips.len();
+            strips.extend([a, b, c]);
+            loop {
+                // each triangle is wound along the last edge of the strip, in alternating turns
+                let [u, v] = [strips[strips.len() - 2], strips[strips.len() - 1]];
+                let (x, y) = if (strips.len() - strip_start) % 2 == 0 {(u, v)} else {(v, u)};
+                let Some(t) = unused_across(&used, x, y) else { break };
+                used[t] = true;
+                let tri = tris[t];
+                let at = (0 .. 3).find(|&k| tri[k] == x && tri[(k + 1) % 3] == y).unwrap();
+                strips.push(tri[(at + 2) % 3]);
             }
-        );
-        tess.tessellate_path(&path, &opts, &mut buf_builder)
-            .map_err(|e| Error::Tessellation(e))?;
-
-        if self.config.extrude {
-            // find boundary edges
-            let mut edge_set = std::collections::HashMap::new();
-            bufs.indices[i_base as usize ..]
-                .array_chunks().copied()
-                .flat_map(|[a, b, c]| [(a, b), (b, c), (c, a)])
-                .for_each(|(a, b)| {
-                    let key = if b < a {(b, a)} else {(a, b)};
-                    use std::collections::hash_map::Entry;
-                    match edge_set.entry(key) {
-                        Entry::Occupied(e) => { e.remove(); },
-                        Entry::Vacant(e)   => { e.insert(()); },
-                    }
-                });
+        }
+        strips
+    }
+
+    /// Overwrites this mesh with a copy of `other`, reusing this mesh's buffers.
+    fn copy_from(&mut self, other: &Mesh) {
+        self.bbox = other.bbox;
+        self.indices.clone_from(&other.indices);
+        self.vertices.clone_from(&other.vertices);
+        self.normals.clone_from(&other.normals);
+        self.uvs.clone_from(&other.uvs);
+        self.submeshes.clone_from(&other.submeshes);
+    }
+
+    /// Appends the triangles of `other`, translated by `offset`, to this mesh.
+    fn append_translated(&mut self, other: &Mesh, offset: [f32; 3]) {
+        if other.vertices.is_empty() { return; }
 
-            // add rear face
-            let v_rear_base = bufs.vertices.len();
-            bufs.vertices.extend_from_within(v_base as usize ..);
-            for v in &mut bufs.vertices[v_rear_base..] { v[2] = -z; }
+        let bbox = other.bbox.translated(offset);
+        self.bbox = if self.vertices.is_empty() {bbox} else {self.bbox.union(&bbox)};
 
-            let i_rear_base = bufs.indices.len();
-            bufs.indices.extend_from_within(i_base as usize ..);
-            for [a, _, c] in bufs.indices[i_rear_base..].array_chunks_mut() {
-                std::mem::swap(a, c);
+        let index_base = self.indices.len() as u32;
+        for (kind, range) in &other.submeshes {
+            let range = range.start + index_base .. range.end + index_base;
+            match self.submeshes.last_mut() {
+                Some((last, last_range)) if last == kind && last_range.end == range.start => {
+                    last_range.end = range.end;
+                }
+                _ => self.submeshes.push((*kind, range)),
             }
+        }
+
+        let base = self.vertices.len() as u32;
+        self.vertices.extend(other.vertices.iter().map(|&v| add(v, offset)));
+        self.normals.extend_from_slice(&other.normals);
+        self.uvs.extend_from_slice(&other.uvs);
+        self.indices.extend(other.indices.iter().map(|i| i + base));
+    }
+
+    /// Copies the triangles in `indices` into a new, independent [Mesh], keeping only the
+    /// vertices they reference.
+    fn submesh(&self, indices: &[u32]) -> Mesh {
+        let mut remap = vec![u32::MAX; self.vertices.len()];
+        let mut vertices = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        let indices = indices.iter()
+            .map(|&i| {
+                let new = &mut remap[i as usize];
+                if *new == u32::MAX {
+                    *new = vertices.len() as u32;
+                    vertices.push(self.vertices[i as usize]);
+                    normals.extend(self.normals.get(i as usize));
+                    uvs.extend(self.uvs.get(i as usize));
+                }
+                *new
+            })
+            .collect();
+        let bbox = BoundingBox::from_points(&vertices);
+        Mesh{bbox, indices, vertices, normals, uvs, submeshes: Vec::new()}
+    }
+
+    /// The range of `indices` holding the part `kind` of a single glyph's mesh.
+    fn part(&self, kind: SubmeshKind) -> std::ops::Range<usize> {
+        self.submeshes.iter()
+            .find(|(k, _)| *k == kind)
+            .map_or(0 .. 0, |(_, range)| range.start as usize .. range.end as usize)
+    }
+
+    /// This mesh, generated in the default convention, moved into the coordinate `system`.
+    fn in_coordinates(mut self, system: CoordinateSystem) -> Mesh {
+        if system == CoordinateSystem::YUpRightHanded { return self; }
 
-            // add sides
-            let r = v_rear_base as u32 - v_base;
-            bufs.indices.extend(
-                edge_set.into_keys()
-                    .flat_map(|(a, b)| [a, b, b+r, a+r, a, b+r])
-            );
+        for v in self.vertices.iter_mut().chain(&mut self.normals) { *v = system.map(*v); }
+        self.bbox = self.bbox.in_coordinates(system);
+        if system == CoordinateSystem::YUpLeftHanded {
+            for [_, b, c] in self.indices.as_chunks_mut().0 { std::mem::swap(b, c); }
+        }
+        self
+    }
+
+    /// Copies the parts of this mesh that `faces` includes into a new [Mesh], keeping only the
+    /// vertices they reference.
+    fn with_faces(&self, faces: FaceFlags) -> Mesh {
+        let mut indices = Vec::new();
+        let mut submeshes = Vec::new();
+        for (kind, range) in self.submeshes.iter().filter(|(kind, _)| faces.contains(*kind)) {
+            let start = indices.len() as u32;
+            indices.extend_from_slice(&self.indices[range.start as usize .. range.end as usize]);
+            submeshes.push((*kind, start .. indices.len() as u32));
         }
 
-        let lt::VertexBuffers{indices, vertices} = bufs;
-        Ok(Mesh{bbox, indices, vertices})
+        let mut mesh = self.submesh(&indices);
+        mesh.submeshes = submeshes;
+        mesh
     }
 }
 
-struct Bridge<B>(ltpb::NoAttributes<B>) where
-    B: ltpb::PathBuilder;
+/// The caps and side walls of an extruded glyph, as independent meshes.
+///
+/// Each mesh has its own vertex and index buffers. For a flat glyph, `rear` and `sides` are
+/// empty.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SplitMesh {
+    /// The front cap, facing +Z.
+    pub front: Mesh,
+    /// The rear cap, facing -Z.
+    pub rear: Mesh,
+    /// The side walls joining the caps.
+    pub sides: Mesh,
+}
 
-impl<B> ttf_parser::OutlineBuilder for Bridge<B> where
-    B: ltpb::PathBuilder,
-{
-    fn move_to(&mut self, x: f32, y: f32) { self.0.begin([x, y].into()); }
-    fn line_to(&mut self, x: f32, y: f32) { self.0.line_to([x, y].into()); }
-    fn close(&mut self) { self.0.close(); }
+/// The centroid of the triangles in `indices`, weighting each by its area, or `None` if they have
+/// no area.
+fn surface_centroid(vertices: &[[f32; 3]], indices: &[u32]) -> Option<[f32; 3]> {
+    let (sum, total_area) = indices
+        .as_chunks().0.iter()
+        .map(|t| t.map(|i| vertices[i as usize]))
+        .fold(([0.; 3], 0.), |(sum, total_area), [a, b, c]| {
+            let (u, v) = (sub(b, a), sub(c, a));
+            let area = length(cross(u, v)) / 2.;
+            let centroid: [f32; 3] = std::array::from_fn(|i| (a[i] + b[i] + c[i]) / 3.);
+            (std::array::from_fn(|i| sum[i] + centroid[i] * area), total_area + area)
+        });
+    (total_area > 0.).then(|| sum.map(|x| x / total_area))
+}
 
-    fn quad_to(&mut self, xc: f32, yc: f32, x: f32, y: f32) {
-        self.0.quadratic_bezier_to([xc, yc].into(), [x, y].into());
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[1]*b[2] - a[2]*b[1], a[2]*b[0] - a[0]*b[2], a[0]*b[1] - a[1]*b[0]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0]*b[0] + a[1]*b[1] + a[2]*b[2]
+}
+
+fn length(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    let l = length(a);
+    if l > 0. {a.map(|x| x / l)} else {a}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Config {
+    /// The largest distance, in mesh units, between the glyph's curves and the straight segments
+    /// that approximate them. It also bounds the flattening of round joins and of the stroke
+    /// tessellator. Smaller values give smoother curves from more triangles;
+    /// [Config::tolerance_for] derives one from an on-screen size. Defaults to 0.1.
+    pub tolerance: f32,
+
+    /// The depth to extrude glyphs to along the Z axis, in the same units as the glyph outline,
+    /// or `None` for flat meshes. Extruded meshes are centred on z = 0.
+    pub extrude: Option<f32>,
+
+    /// The direction glyphs are extruded in, from the front cap towards the rear cap, which is
+    /// the front cap moved by `extrude` along it. An oblique direction gives slanted side walls,
+    /// as for cast-shadow text; the caps stay parallel to the XY plane, with the front cap at
+    /// the outline's position, and the extrusion stays centred on z = 0. Only the direction of
+    /// the vector matters. Its Z component must be negative, and not negligible: meshing fails
+    /// with [Error::InvalidExtrudeDirection] for directions that are within about 0.06° of the XY
+    /// plane or point towards +Z, rather than flipping them. The default is -Z.
+    pub extrude_direction: [f32; 3],
+
+    /// How far the rear cap is scaled down towards the centroid of the front cap, from 0 for a
+    /// straight extrusion to 1, which shrinks it to a point, for frustum- or pyramid-like glyphs.
+    /// The side walls narrow linearly with depth. Negative values flare glyphs out instead. This
+    /// has no effect unless `extrude` is set.
+    pub taper: f32,
+
+    /// The angle in degrees that glyphs turn by about the centroid of their front cap, from the
+    /// front cap to the rear cap; positive angles turn counter-clockwise, seen from +Z, with
+    /// depth. The side walls are split into segments that each turn by at most 5°. This has no
+    /// effect unless `extrude` is set.
+    pub twist_degrees: f32,
+
+    /// The units that glyph outlines are scaled into. The other lengths in this [Config], such
+    /// as `tolerance` and `extrude`, are measured in the same units.
+    pub units: Units,
+
+    /// If set, glyphs are scaled so that the face's cap height matches this value, instead of
+    /// by `units`. Generators for different faces sharing the same target produce glyphs with
+    /// matching cap heights on a common baseline.
+    pub target_cap_height: Option<f32>,
+
+    /// Whether to reorient contours by their nesting before filling, so that holes are cut out
+    /// regardless of the winding direction used by the font. This repairs fonts with uniformly
+    /// wound contours, but can misclassify contours that partially overlap each other.
+    pub normalize_winding: bool,
+
+    /// The rule deciding which regions of the outline are filled. With [FillRule::EvenOdd],
+    /// contours are also reoriented by their nesting, as with `normalize_winding`, so that the
+    /// side walls of holes face into them.
+    pub fill_rule: FillRule,
+
+    /// The distance to offset each contour outward by before tessellation, in mesh units, to
+    /// synthesize a bolder weight. The offset contours are then merged into the outline of the
+    /// region they fill by [FillRule::NonZero], so that where they overlap, as where strokes
+    /// meet, the caps are filled whatever the `fill_rule`, and holes that close up are gone.
+    /// Contours are kept apart when `contour_depth` is set, as its depths are looked up by
+    /// contour. Negative values thin glyphs instead, which only works while no stroke
+    /// vanishes. Advances are unchanged, so text may need extra `letter_spacing`.
+    pub embolden: f32,
+
+    /// The shape of emboldened outlines at the corners where the offset edges part, such as the
+    /// apex of an "A". The corners where they cross are always mitered.
+    pub embolden_join: StrokeJoin,
+
+    /// How far a mitered corner may reach, relative to the offset distance for `embolden` or to
+    /// half the width of a `stroke`, before it is cut off; as in SVG, 4 cuts off corners sharper
+    /// than about 29°. Values below 1 are treated as 1.
+    pub miter_limit: f32,
+
+    /// The horizontal shift per unit of height applied to outlines before tessellation, to fake
+    /// italics for faces without an italic style. Positive values lean glyphs to the right; `0.2`
+    /// slants them by about 11°. The baseline stays in place.
+    pub slant: f32,
+
+    /// Whether to generate vertex normals: +Z for the front cap, -Z for the rear cap, and the
+    /// face normal of each quad for the side walls. Side walls then get vertices of their own,
+    /// rather than sharing those of the caps.
+    pub generate_normals: bool,
+
+    /// The angle in degrees below which neighbouring side wall faces share smoothed normals,
+    /// so that curved glyphs such as "O" look round, while sharper corners stay hard. This only
+    /// applies when `generate_normals` is set; 0 keeps every face flat, and the caps always keep
+    /// their own normals.
+    pub crease_angle: f32,
+
+    /// How to generate texture coordinates, if at all. Side walls get vertices of their own when
+    /// this is set, rather than sharing those of the caps.
+    pub uv_mapping: Option<UvMapping>,
+
+    /// Where the side walls' texture coordinates wrap around from u = 1 back to 0 on each
+    /// contour. This has no effect unless `uv_mapping` is set.
+    pub uv_seam: UvSeam,
+
+    /// If set, the edge between the front cap and the side walls is bevelled. This has no effect
+    /// unless `extrude` is set.
+    pub bevel: Option<BevelConfig>,
+
+    /// If set, the cross-section of the side walls, for stepped, rounded or ogee profiles, as
+    /// (depth, offset) pairs running from the front cap towards the rear cap. The depth is the
+    /// distance below the front cap, and the offset how far the outline is moved outwards at that
+    /// depth, both in mesh units; negative offsets move it inwards. The front cap is placed at
+    /// the first pair, and the last is joined to the rear cap, which keeps the outline's shape.
+    ///
+    /// Like a bevel, the profile is built by offsetting the outline, so offsets should be small
+    /// compared to the glyph's strokes. This takes the place of `bevel`, and has no effect unless
+    /// `extrude` is set.
+    pub profile: Option<std::sync::Arc<[(f32, f32)]>>,
+
+    /// How the front and rear caps are triangulated.
+    pub cap_triangulation: CapTriangulation,
+
+    /// If set, glyphs are outlined with a stroke instead of being filled, for hollow letters.
+    /// The stroke is centred on the outline and replaces the caps from `cap_triangulation`; when
+    /// extruded, it gets side walls along both of its edges. Where a wide stroke overlaps itself,
+    /// such as in narrow counters, the overlapping parts are left intersecting each other.
+    pub stroke: Option<StrokeConfig>,
+
+    /// If set, gives each contour its own extrusion depth for stepped relief, instead of
+    /// extruding the whole glyph uniformly. The slice holds the depth of the region bounded by
+    /// each contour, indexed by the contour's position within the glyph outline; contours past
+    /// its end are extruded to the full `extrude` depth.
+    ///
+    /// All regions share the rear cap's plane, and their front caps are raised to their own
+    /// depths. A hole takes the depth of the contour it is cut out of, so only outer contours are
+    /// looked up. This only makes sense when regions don't overlap each other. It has no effect
+    /// unless `extrude` is set.
+    pub contour_depth: Option<std::sync::Arc<[f32]>>,
+
+    /// What text is meshed with where the face has no glyph for a character.
+    pub missing_glyph: MissingGlyph,
+
+    /// The coordinate convention that meshes are generated in. The rest of the [Config], and
+    /// the documentation of the crate, describe meshes in the default convention.
+    pub coordinates: CoordinateSystem,
+
+    /// Which parts of glyph meshes are generated. Leaving parts out leaves meshes open: text
+    /// placed against a wall doesn't need its rear cap, and shadow volumes only need the side
+    /// walls. Flat glyphs only have a front cap.
+    pub faces: FaceFlags,
+}
+
+/// Which parts of glyph meshes are generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FaceFlags {
+    /// Whether to generate the front cap, facing +Z.
+    pub front: bool,
+    /// Whether to generate the rear cap, facing -Z.
+    pub back: bool,
+    /// Whether to generate the side walls.
+    pub sides: bool,
+}
+
+impl FaceFlags {
+    fn contains(&self, kind: SubmeshKind) -> bool {
+        match kind {
+            SubmeshKind::Front => self.front,
+            SubmeshKind::Back  => self.back,
+            SubmeshKind::Sides => self.sides,
+            SubmeshKind::Plate => true,
+        }
     }
+}
 
-    fn curve_to(&mut self, xc0: f32, yc0: f32, xc1: f32, yc1: f32, x: f32, y: f32) {
-        self.0.cubic_bezier_to([xc0, yc0].into(), [xc1, yc1].into(), [x, y].into());
+impl Default for FaceFlags {
+    fn default() -> Self {
+        Self{front: true, back: true, sides: true}
+    }
+}
+
+/// A bevel around the front cap of an extruded glyph.
+///
+/// The front cap is inset by `width`, and joined to the side walls by a quarter-round profile
+/// that drops by `width` along the Z axis. The bevel is built by offsetting the outline, so
+/// `width` should be small compared to the glyph's strokes, or the inset cap folds over itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BevelConfig {
+    /// The inset of the front cap, and the depth of the bevel, in mesh units.
+    pub width: f32,
+
+    /// The number of segments in the bevel's profile. A single segment gives a flat chamfer;
+    /// more approximate a rounded edge.
+    pub segments: u32,
+}
+
+/// A stroke along the outline of a glyph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrokeConfig {
+    /// The width of the stroke, in mesh units.
+    pub width: f32,
+
+    /// The shape of the stroke at corners of the outline.
+    pub join: StrokeJoin,
+}
+
+/// The shape of a stroke at corners of the outline it follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StrokeJoin {
+    /// The edges of the stroke are extended until they meet, up to a limit for sharp corners,
+    /// beyond which the corner is cut off.
+    #[default]
+    Miter,
+
+    /// The corner is rounded off with an arc.
+    Round,
+
+    /// The corner is cut off straight across.
+    Bevel,
+}
+
+/// How texture coordinates are generated for glyph meshes.
+///
+/// The caps are always mapped with a planar projection along Z. The side walls are unwrapped
+/// around each contour of the outline, with u running along the contour and v running from the
+/// rear cap to the front cap; the seam lies at an arbitrary vertex of each contour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UvMapping {
+    /// The caps use their x and y coordinates relative to the glyph's origin. On the side walls,
+    /// u is the distance along the contour and v the height above the rear cap. This keeps texel
+    /// density uniform across glyphs.
+    Raw,
+
+    /// The caps are mapped from the glyph's bounding box to the unit square. On the side walls,
+    /// u runs from 0 to 1 around each contour and v from 0 at the rear cap to 1 at the front.
+    GlyphBounds,
+
+    /// Like [UvMapping::GlyphBounds], except that the caps of text generated with
+    /// [MeshGenerator::generate_text] are mapped from the bounding box of the whole text, so a
+    /// single texture spans the string. Single glyphs are mapped as for
+    /// [UvMapping::GlyphBounds].
+    TextBounds,
+}
+
+/// Where the seam in the texture coordinates of the side walls lies around each contour.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UvSeam {
+    /// At an arbitrary vertex of each contour, often partway along a stroke.
+    #[default]
+    Auto,
+
+    /// At the vertex of each contour nearest the given point, relative to the glyph's origin
+    /// and in mesh units, so that the seam can be hidden in a corner; a point below and to the
+    /// left of the glyph puts it at the bottom-left-most vertex.
+    AtPoint([f32; 2]),
+}
+
+/// Maps `v` from `bbox` to the unit square, dropping z.
+fn planar_uv(v: [f32; 3], bbox: &BoundingBox) -> [f32; 2] {
+    std::array::from_fn(|i| {
+        let size = bbox.maxs[i] - bbox.mins[i];
+        if size > 0. {(v[i] - bbox.mins[i]) / size} else {0.}
+    })
+}
+
+/// The units that generated meshes are measured in.
+///
+/// Normalizing by the face's height gives each face the same line spacing, but faces with tall
+/// ascenders or deep descenders then have visibly smaller glyphs; normalizing by cap height
+/// matches the visual size of text across faces instead.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Units {
+    /// Scales the face so that its height, from descender to ascender, is 1.
+    #[default]
+    FaceHeight,
+
+    /// Leaves outlines in the face's own font units, unscaled.
+    FontUnits,
+
+    /// Scales the face so that its em square is 1, as in typographic point sizes.
+    Em,
+
+    /// Scales the face so that its cap height is 1, falling back to [Units::FaceHeight] for
+    /// faces without a cap height or an 'H' glyph to measure one from.
+    CapHeight,
+
+    /// Scales font units by the given factor.
+    Scale(f32),
+}
+
+/// What text is meshed with where the face has no glyph for a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MissingGlyph {
+    /// Use the face's missing glyph, `.notdef`.
+    #[default]
+    NotdefMesh,
+
+    /// Leave the character out, without advancing.
+    Skip,
+
+    /// Fail with [Error::MissingGlyph], reporting the character.
+    Error,
+
+    /// Use a synthesized hollow box, spaced like the face's missing glyph and as tall as its
+    /// capitals, for faces whose `.notdef` is empty or unhelpful.
+    TofuBox,
+}
+
+/// Stands in for the placeholder box of [MissingGlyph::TofuBox] where glyphs are expected. Glyph
+/// IDs run up to one less than the number of glyphs, which is at most `u16::MAX`, so no face has
+/// a glyph with this ID.
+const TOFU: GlyphId = GlyphId(u16::MAX);
+
+/// Outlines the placeholder box of [MissingGlyph::TofuBox] into `builder`, in font units.
+fn outline_tofu(face: &ttf_parser::Face<'_>, builder: &mut dyn ttf_parser::OutlineBuilder) {
+    let em = face.units_per_em() as f32;
+    let advance = face.glyph_hor_advance(GlyphId(0)).map_or(0.5 * em, f32::from);
+    let height = raw_cap_height(face).unwrap_or(0.7 * em);
+    let stroke = 0.08 * em;
+
+    // the outer edge winds counter-clockwise, and the inner edge clockwise to cut it out
+    let outer = [0.1 * advance, 0., 0.9 * advance, height];
+    let inner = [outer[0] + stroke, stroke, outer[2] - stroke, height - stroke];
+    let [x0, y0, x1, y1] = outer;
+    for (x, y) in [(x0, y0), (x1, y0), (x1, y1), (x0, y1)] {
+        if x == x0 && y == y0 {builder.move_to(x, y)} else {builder.line_to(x, y)}
+    }
+    builder.close();
+
+    let [x0, y0, x1, y1] = inner;
+    if x0 < x1 && y0 < y1 {
+        builder.move_to(x0, y0);
+        for (x, y) in [(x0, y1), (x1, y1), (x1, y0)] { builder.line_to(x, y); }
+        builder.close();
+    }
+}
+
+/// The coordinate convention of generated meshes.
+///
+/// Glyphs are laid out with x running along the text and y running up, and are extruded away
+/// from the viewer. Each convention maps these onto its own axes, reversing the winding of
+/// triangles where it mirrors them, so that front faces stay front faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CoordinateSystem {
+    /// Y up and +Z towards the viewer, right-handed, as in OpenGL and glTF. Glyphs lie in the XY
+    /// plane, with their front caps facing +Z.
+    #[default]
+    YUpRightHanded,
+
+    /// Z up and -Y towards the viewer, right-handed, as in Blender and most 3D printing tools.
+    /// Glyphs stand in the XZ plane, with their front caps facing -Y.
+    ZUpRightHanded,
+
+    /// Y up and -Z towards the viewer, left-handed, as in Unity. Glyphs lie in the XY plane,
+    /// with their front caps facing -Z.
+    YUpLeftHanded,
+}
+
+impl CoordinateSystem {
+    /// Maps a point or direction from the default convention into this one.
+    fn map(self, [x, y, z]: [f32; 3]) -> [f32; 3] {
+        match self {
+            Self::YUpRightHanded => [x, y, z],
+            Self::ZUpRightHanded => [x, -z, y],
+            Self::YUpLeftHanded  => [x, y, -z],
+        }
+    }
+}
+
+/// The rule deciding which regions of an outline are inside it, from the contours that enclose
+/// them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// A region is filled if the contours around it wind around it a non-zero number of times,
+    /// counting clockwise contours against counter-clockwise ones. This is the rule of TrueType
+    /// and CFF outlines.
+    #[default]
+    NonZero,
+
+    /// A region is filled if an odd number of contours enclose it, whichever way they wind. This
+    /// suits outlines with uniformly wound contours, such as some converted from SVG.
+    EvenOdd,
+}
+
+/// The triangulation used for the caps of a glyph mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CapTriangulation {
+    /// Fill the outline with a general-purpose tessellator. This handles any outline.
+    #[default]
+    Tessellated,
+
+    /// Triangulate each contour as a fan from a vertex inserted at its centroid, so that every
+    /// triangle radiates outward from the centre. This suits radial vertex animations.
+    ///
+    /// This is only correct for contours that are star-shaped around their centroid and have no
+    /// holes: holes are fanned like any other contour rather than cut out, and concave contours
+    /// produce overlapping triangles.
+    FanFromCentroid,
+}
+
+/// The largest distance, in pixels, that [Config::tolerance_for] lets a flattened curve stray
+/// from the true one on screen.
+const SCREEN_TOLERANCE_PX: f32 = 0.25;
+
+impl Config {
+    /// Get a `tolerance` for glyphs that will be drawn with one mesh unit spanning
+    /// `target_height_px` pixels on screen, such that flattened curves stay within a quarter of
+    /// a pixel of the true outline. With the default [Units], one mesh unit is the face's height,
+    /// so this is roughly the pixel size of a line of text. Small labels then get fewer
+    /// triangles, while large titles stay smooth.
+    ///
+    /// Arguments:
+    /// * `target_height_px`: The on-screen size of one mesh unit, in pixels. Must be positive.
+    pub fn tolerance_for(target_height_px: f32) -> f32 {
+        SCREEN_TOLERANCE_PX / target_height_px
+    }
+
+    /// Half the distance along the Z axis between the front and rear caps.
+    fn half_depth(&self) -> f32 {
+        let dz = normalize(self.extrude_direction)[2];
+        self.extrude.map_or(0., |depth| depth * dz.abs() / 2.)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            tolerance: lt::FillOptions::DEFAULT_TOLERANCE,
+            extrude: Some(1.0),
+            extrude_direction: [0., 0., -1.],
+            taper: 0.,
+            twist_degrees: 0.,
+            units: Units::FaceHeight,
+            target_cap_height: None,
+            normalize_winding: false,
+            fill_rule: FillRule::NonZero,
+            embolden: 0.,
+            embolden_join: StrokeJoin::Miter,
+            miter_limit: MITER_LIMIT,
+            slant: 0.,
+            generate_normals: false,
+            crease_angle: 0.,
+            uv_mapping: None,
+            uv_seam: UvSeam::Auto,
+            bevel: None,
+            profile: None,
+            cap_triangulation: CapTriangulation::Tessellated,
+            stroke: None,
+            contour_depth: None,
+            missing_glyph: MissingGlyph::NotdefMesh,
+            coordinates: CoordinateSystem::YUpRightHanded,
+            faces: FaceFlags::default(),
+        }
     }
 }
 
+pub use ttf_parser::{GlyphId, Tag};
+#[cfg(feature = "rkyv")]
+pub use archive::{ArchivedMeshSet, MeshSet};
+pub use border::BorderedMesh;
+pub use collection::{faces, FaceInfo};
+pub use disk_cache::DiskCache;
+pub use color::{ColorLayer, ColorMesh};
+pub use owned::OwnedMeshGenerator;
+pub use text::{
+    Align, GlyphMesh, InstancedText, Plate, PlacedGlyph, TextLayout, TextMesh, TextMetrics,
+    WritingMode,
+};
+pub use variation::NamedInstance;
+pub use vertex::{MeshSink, Vertex, VertexAttributes, VertexBuffers, VertexCtor};
+
+/// Generates glyph meshes for a font.
+///
+/// The generator only borrows the font data for `'face`; it never copies the font, which makes
+/// it suitable for memory-mapped fonts.
+///
+/// Each glyph is tessellated once: its mesh is cached, and cloned for later requests. The cache
+/// grows with every distinct glyph meshed, and can be emptied with
+/// [clear_cache](Self::clear_cache). The generator also keeps its tessellators and scratch
+/// buffers between calls, to avoid reallocating them for every glyph. It can be sent to another
+/// thread, but is not [Sync]; use one generator per thread.
+pub struct MeshGenerator<'face> {
+    face: ttf_parser::Face<'face>,
+    config: Config,
+    scale: f32,
+    tessellator: std::cell::RefCell<Tessellator>,
+    cache: std::cell::RefCell<std::collections::HashMap<GlyphId, Mesh>>,
+}
+
+/// The tessellators and scratch buffers that glyphs are meshed with, kept between glyphs so that
+/// their allocations are reused.
+#[derive(Default)]
+struct Tessellator {
+    fill: lt::FillTessellator,
+    stroke: lt::StrokeTessellator,
+    /// The vertices and indices of the mesh being built.
+    buffers: lt::VertexBuffers<[f32; 3], u32>,
+    /// The front cap as laid out by the outline, before any bevel or profile moves it.
+    outline: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    /// The edges of the front cap that haven't been paired with a neighbouring triangle's yet.
+    edges: std::collections::HashMap<(u32, u32), (u32, u32)>,
+}
+
+use lyon_tessellation::{self as lt, path as ltp};
+
+impl<'face> MeshGenerator<'face> {
+    /// Creates a new [MeshGenerator].
+    ///
+    /// The generator keeps a clone of `face`, which only copies the face's slices of the font
+    /// data, not the data itself, and so borrows the data for `'face`.
+    ///
+    /// Arguments:
+    /// * `face`: The face that will be used for rasterizing.
+    pub fn new(face: &ttf_parser::Face<'face>) -> Self {
+        Self::new_with_config(face, Config::default())
+    }
+
+    /// Creates a new [MeshGenerator] with custom quality settings.
+    ///
+    /// Arguments:
+    /// * `face`: The face that will be used for rasterizing.
+    /// * `quality`: The [QualitySettings] that should be used.
+    pub fn new_with_config(face: &ttf_parser::Face<'face>, config: Config) -> Self {
+        let scale = mesh_scale(face, &config);
+        let tessellator = Default::default();
+        let cache = Default::default();
+        Self{face: face.clone(), config, scale, tessellator, cache}
+    }
+
+    /// Creates a new [MeshGenerator] by parsing a face from borrowed font data, such as a
+    /// memory-mapped file.
+    ///
+    /// The generator borrows `data` rather than copying it, so it can't outlive it:
+    ///
+    /// ```compile_fail,E0597
+    /// let generator = {
+    ///     let data = std::fs::read("font.ttf").unwrap();
+    ///     trianglyph::MeshGenerator::from_bytes(&data, 0).unwrap()
+    /// };
+    /// generator.cache_len();
+    /// ```
+    ///
+    /// Arguments:
+    /// * `data`: The font data. The generator cannot outlive it.
+    /// * `index`: The index of the face within a font collection, or 0.
+    ///
+    /// Returns:
+    /// A [Result] containing the [MeshGenerator] if successful, otherwise an [Error].
+    pub fn from_bytes(data: &'face [u8], index: u32) -> Result<Self> {
+        Self::from_bytes_with_config(data, index, Config::default())
+    }
+
+    /// Creates a new [MeshGenerator] with custom quality settings by parsing a face from
+    /// borrowed font data.
+    ///
+    /// Arguments:
+    /// * `data`: The font data. The generator cannot outlive it.
+    /// * `index`: The index of the face within a font collection, or 0.
+    /// * `config`: The [Config] that should be used.
+    ///
+    /// Returns:
+    /// A [Result] containing the [MeshGenerator] if successful, otherwise an [Error].
+    pub fn from_bytes_with_config(data: &'face [u8], index: u32, config: Config) -> Result<Self> {
+        let face = ttf_parser::Face::parse(data, index).map_err(Error::FontParse)?;
+        Ok(Self::new_with_config(&face, config))
+    }
+
+    /// Get the face used by this [MeshGenerator].
+    pub fn face(&self) -> &ttf_parser::Face<'face> {
+        &self.face
+    }
+
+    /// Get the size of the face's em square, in the same units as generated meshes.
+    ///
+    /// Meshes are scaled by [Config::units], which by default normalizes them so that the face's
+    /// height (ascender to descender) is 1, or so that the face's cap height is
+    /// [Config::target_cap_height] if that is set. The em square is usually somewhat smaller
+    /// than the face's height.
+    pub fn em_size(&self) -> f32 {
+        self.face.units_per_em() as f32 * self.scale
+    }
+
+    /// Get the factor that converts mesh units to points, at the given point size.
+    ///
+    /// A font's point size is the size of its em square, so text set at `pt` points has an em of
+    /// `pt` points. One em is [MeshGenerator::em_size] mesh units, so the factor is
+    /// `pt / em_size()`.
+    ///
+    /// Arguments:
+    /// * `pt`: The point size.
+    pub fn point_size_scale(&self, pt: f32) -> f32 {
+        pt / self.em_size()
+    }
+
+    /// Get the factor that converts points at the given point size to mesh units. This is the
+    /// inverse of [MeshGenerator::point_size_scale].
+    ///
+    /// Arguments:
+    /// * `pt`: The point size.
+    pub fn normalized_from_point_size(&self, pt: f32) -> f32 {
+        self.em_size() / pt
+    }
+
+    /// Get the recommended distance between consecutive baselines, in the same units as
+    /// generated meshes.
+    ///
+    /// This is the ascender minus the descender plus the line gap. These are taken from the
+    /// `OS/2` table's typographic metrics if its `USE_TYPO_METRICS` flag is set, and otherwise
+    /// from the `hhea` table, falling back to the `OS/2` typographic and then Windows metrics
+    /// where `hhea` has none.
+    pub fn line_height(&self) -> f32 {
+        let face = &self.face;
+        let raw = face.ascender() as f32 - face.descender() as f32 + face.line_gap() as f32;
+        raw * self.scale
+    }
+
+    /// Get the ascender of the face, the height above the baseline that its tallest glyphs
+    /// typically reach, in the same units as generated meshes.
+    ///
+    /// This is taken from the same table as [MeshGenerator::line_height].
+    pub fn ascender(&self) -> f32 {
+        self.face.ascender() as f32 * self.scale
+    }
+
+    /// Get the descender of the face, the height of the lowest point that its glyphs typically
+    /// reach relative to the baseline, in the same units as generated meshes. This is usually
+    /// negative.
+    ///
+    /// This is taken from the same table as [MeshGenerator::line_height].
+    pub fn descender(&self) -> f32 {
+        self.face.descender() as f32 * self.scale
+    }
+
+    /// Get the line gap of the face, the extra space recommended between the descender of one
+    /// line and the ascender of the next, in the same units as generated meshes.
+    ///
+    /// This is taken from the same table as [MeshGenerator::line_height].
+    pub fn line_gap(&self) -> f32 {
+        self.face.line_gap() as f32 * self.scale
+    }
+
+    /// Get the x-height of the face, in the same units as generated meshes.
+    ///
+    /// This is read from the `OS/2` table if present, and otherwise measured from the 'x' glyph.
+    pub fn x_height(&self) -> Option<f32> {
+        raw_x_height(&self.face).map(|h| h * self.scale)
+    }
+
+    /// Get the cap height of the face, in the same units as generated meshes.
+    ///
+    /// This is read from the `OS/2` table if present, and otherwise measured from the 'H' glyph.
+    pub fn cap_height(&self) -> Option<f32> {
+        raw_cap_height(&self.face).map(|h| h * self.scale)
+    }
+
+    /// Generates a new [Mesh] from the loaded font and the given `glyph`.
+    ///
+    /// The mesh is placed at the glyph's origin: x = 0 is the start of its advance, and y = 0 is
+    /// the baseline, so [ascender](Self::ascender), [descender](Self::descender) and the other
+    /// metrics of the generator can be used as offsets from it directly.
+    ///
+    /// With the `svg` feature, a glyph without an outline is outlined from the filled paths of its
+    /// document in the face's `SVG` table, if it has one.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    ///
+    /// Returns:
+    /// A [Result] containing the [Mesh] if successful, otherwise an [Error].
+    pub fn generate_mesh(&self, glyph: GlyphId) -> Result<Mesh> {
+        self.cached_glyph(glyph).map(|mesh| mesh.in_coordinates(self.config.coordinates))
+    }
+
+    /// Empties the glyph mesh cache.
+    pub fn clear_cache(&mut self) {
+        self.cache.get_mut().clear();
+    }
+
+    /// Get the number of glyph meshes in the cache.
+    pub fn cache_len(&self) -> usize {
+        self.cache.borrow().len()
+    }
+
+    /// Generates a [Mesh] for each of the given `glyphs`, in order, as
+    /// [generate_mesh](Self::generate_mesh) does, such as to prepare a whole character set.
+    ///
+    /// The batch shares the generator's tessellators, scratch buffers and glyph cache, so meshing
+    /// each glyph only allocates its own mesh, and glyphs that appear more than once are only
+    /// meshed once.
+    ///
+    /// Arguments:
+    /// * `glyphs`: The glyphs to be meshed.
+    ///
+    /// Returns:
+    /// A [Result] containing the meshes if successful, otherwise the first [Error].
+    pub fn generate_meshes(&self, glyphs: &[GlyphId]) -> Result<Vec<Mesh>> {
+        glyphs.iter().map(|&glyph| self.generate_mesh(glyph)).collect()
+    }
+
+    /// Generates the [Mesh] for the given `glyph` into an existing mesh, as
+    /// [generate_mesh](Self::generate_mesh) does, reusing the mesh's buffers.
+    ///
+    /// Once the buffers have grown to fit the glyphs being meshed, and the glyphs are cached,
+    /// this doesn't allocate, which suits text that is regenerated every frame. A glyph that
+    /// isn't cached yet is tessellated in the generator's own tessellators and scratch buffers,
+    /// which are kept between calls, so only its copy in the cache is allocated.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    /// * `mesh`: The mesh to overwrite with the glyph's mesh.
+    ///
+    /// Returns:
+    /// An empty [Result] if successful, otherwise an [Error]. On error, `mesh` is left as it was.
+    pub fn generate_mesh_into(&self, glyph: GlyphId, mesh: &mut Mesh) -> Result<()> {
+        self.cache_glyph(glyph)?;
+        mesh.copy_from(&self.cache.borrow()[&glyph]);
+        *mesh = std::mem::take(mesh).in_coordinates(self.config.coordinates);
+        Ok(())
+    }
+
+    /// Meshes `glyph` with the generator's own [Config], or clones its mesh from the cache.
+    fn cached_glyph(&self, glyph: GlyphId) -> Result<Mesh> {
+        self.cache_glyph(glyph)?;
+        Ok(self.cache.borrow()[&glyph].clone())
+    }
+
+    /// Meshes `glyph` into the cache, unless it is already there.
+    fn cache_glyph(&self, glyph: GlyphId) -> Result<()> {
+        if !self.cache.borrow().contains_key(&glyph) {
+            let meshed = self.mesh_glyph(glyph, &self.config)?;
+            self.cache.borrow_mut().insert(glyph, meshed);
+        }
+        Ok(())
+    }
+
+    fn mesh_glyph(&self, glyph: GlyphId, config: &Config) -> Result<Mesh> {
+        let tess = &mut self.tessellator.borrow_mut();
+        mesh_face_glyph(&self.face, self.scale, glyph, config, tess)
+    }
+
+    /// The outline of `glyph` in mesh units, flattened to `tolerance`, or `None` if it has none.
+    fn outline(&self, glyph: GlyphId, tolerance: f32) -> Option<OutlineBuilder> {
+        let mut builder = OutlineBuilder::new(self.scale, tolerance);
+        outline_face_glyph(&self.face, glyph, &mut builder).then_some(builder)
+    }
+
+    /// Outlines `glyph` into `builder` in font units, returning whether it has an outline.
+    fn outline_into(&self, glyph: GlyphId, builder: &mut dyn ttf_parser::OutlineBuilder) -> bool {
+        outline_face_glyph(&self.face, glyph, builder)
+    }
+
+    /// Generates the front cap, rear cap and side walls of the given `glyph` as separate meshes.
+    ///
+    /// Together, the three meshes hold the same triangles as [MeshGenerator::generate_mesh]
+    /// produces.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    ///
+    /// Returns:
+    /// A [Result] containing the [SplitMesh] if successful, otherwise an [Error].
+    pub fn generate_mesh_split(&self, glyph: GlyphId) -> Result<SplitMesh> {
+        let mesh = self.cached_glyph(glyph)?;
+        let [front, rear, sides] = [SubmeshKind::Front, SubmeshKind::Back, SubmeshKind::Sides]
+            .map(|kind| {
+                let part = mesh.submesh(&mesh.indices[mesh.part(kind)]);
+                part.in_coordinates(self.config.coordinates)
+            });
+        Ok(SplitMesh{front, rear, sides})
+    }
+
+    /// Generates a flat, two-sided [Mesh] for the given `glyph`, which can be rendered from
+    /// either side without disabling back-face culling.
+    ///
+    /// The cap is tessellated once and stored twice at z = 0: first wound counter-clockwise when
+    /// seen from +Z with +Z normals, then wound the other way with -Z normals. Extrusion settings
+    /// in the [Config] are ignored.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    ///
+    /// Returns:
+    /// A [Result] containing the [Mesh] if successful, otherwise an [Error].
+    pub fn generate_flat_double_sided(&self, glyph: GlyphId) -> Result<Mesh> {
+        let config = Config{extrude: None, generate_normals: true, ..self.config.clone()};
+        let mut mesh = self.mesh_glyph(glyph, &config)?;
+
+        let n = mesh.vertices.len() as u32;
+        let n_indices = mesh.indices.len();
+        mesh.vertices.extend_from_within(..);
+        mesh.normals.extend(std::iter::repeat_n([0., 0., -1.], n as usize));
+        mesh.uvs.extend_from_within(..);
+        mesh.indices.extend_from_within(..);
+        for [a, b, c] in mesh.indices[n_indices ..].as_chunks_mut().0 {
+            [*a, *b, *c] = [*c + n, *b + n, *a + n];
+        }
+        mesh.submeshes.push((SubmeshKind::Back, n_indices as u32 .. mesh.indices.len() as u32));
+        Ok(mesh.in_coordinates(self.config.coordinates))
+    }
+}
+
+/// The entry `name_id` in the `name` table of `face`, preferring US English where there are
+/// several languages.
+fn face_name(face: &ttf_parser::Face<'_>, name_id: u16) -> Option<String> {
+    const ENGLISH_US: u16 = 0x0409;
+    let names = face.names().into_iter().filter(|name| name.name_id == name_id);
+    let (english, others): (Vec<_>, Vec<_>) = names.partition(|name| {
+        name.platform_id == ttf_parser::PlatformId::Windows && name.language_id == ENGLISH_US
+    });
+    english.into_iter().chain(others).find_map(|name| name.to_string())
+}
+
+/// The scale from font units to mesh units for `face`.
+fn mesh_scale(face: &ttf_parser::Face<'_>, config: &Config) -> f32 {
+    let per_height = 1. / face.height() as f32;
+    if let Some((target, cap)) = config.target_cap_height.zip(raw_cap_height(face)) {
+        return target / cap;
+    }
+    match config.units {
+        Units::FaceHeight => per_height,
+        Units::FontUnits  => 1.,
+        Units::Em         => 1. / face.units_per_em() as f32,
+        Units::CapHeight  => raw_cap_height(face).map_or(per_height, |cap| 1. / cap),
+        Units::Scale(s)   => s,
+    }
+}
+
+/// The cap height of `face` in font units, from the `OS/2` table or else measured from 'H'.
+fn raw_cap_height(face: &ttf_parser::Face<'_>) -> Option<f32> {
+    metric_or_glyph_top(face, face.capital_height(), 'H')
+}
+
+/// The x-height of `face` in font units, from the `OS/2` table or else measured from 'x'.
+fn raw_x_height(face: &ttf_parser::Face<'_>) -> Option<f32> {
+    metric_or_glyph_top(face, face.x_height(), 'x')
+}
+
+fn metric_or_glyph_top(face: &ttf_parser::Face<'_>, metric: Option<i16>, c: char)
+    -> Option<f32>
+{
+    metric
+        .filter(|&h| h > 0)
+        .or_else(|| {
+            let glyph = face.glyph_index(c)?;
+            face.glyph_bounding_box(glyph).map(|bbox| bbox.y_max)
+        })
+        .map(f32::from)
+}
+
+/// Meshes `glyph` of `face`, scaled from font units by `scale`, with `config`. This needs only
+/// the face, so that glyphs can be meshed away from their generator.
+fn mesh_face_glyph(
+    face: &ttf_parser::Face<'_>,
+    scale: f32,
+    glyph: GlyphId,
+    config: &Config,
+    tess: &mut Tessellator,
+) -> Result<Mesh> {
+    let mut builder = OutlineBuilder::new(scale, config.tolerance);
+    match outline_face_glyph(face, glyph, &mut builder) {
+        true => build_mesh(builder, config, tess),
+        false => Ok(Default::default()),
+    }
+}
+
+/// Outlines `glyph` of `face` into `builder` in font units, returning whether it has an outline.
+fn outline_face_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph: GlyphId,
+    builder: &mut dyn ttf_parser::OutlineBuilder,
+) -> bool {
+    let outlined = match glyph {
+        TOFU => { outline_tofu(face, builder); true }
+        _ => face.outline_glyph(glyph, builder).is_some(),
+    };
+    #[cfg(feature = "svg")]
+    let outlined = outlined || svg::outline_glyph(face, glyph, builder);
+    outlined
+}
+
+/// Generates a new [Mesh] from an outline supplied by the caller.
+///
+/// This runs the same tessellation and extrusion as [MeshGenerator::generate_mesh], for outlines
+/// that don't come from a [ttf_parser::Face].
+///
+/// Arguments:
+/// * `builder`: The [OutlineBuilder] holding the outline to be meshed.
+/// * `config`: The [Config] that should be used.
+///
+/// Returns:
+/// A [Result] containing the [Mesh] if successful, otherwise an [Error].
+pub fn build_mesh_from_outline_builder(builder: OutlineBuilder, config: &Config) -> Result<Mesh> {
+    build_mesh(builder, config, &mut Tessellator::default())
+        .map(|mesh| mesh.in_coordinates(config.coordinates))
+}
+
+fn build_mesh(mut builder: OutlineBuilder, config: &Config, tess: &mut Tessellator)
+    -> Result<Mesh>
+{
+    builder.adjust(config);
+
+    let Some([mut x_min, mut y_min, mut x_max, mut y_max]) = builder.bbox else {
+        return Ok(Default::default());
+    };
+
+    let z = config.half_depth();
+
+    // the horizontal offset of the side walls per unit of depth below the front cap
+    let [dx, dy, dz] = normalize(config.extrude_direction);
+    if config.extrude.is_some() && (dz.is_nan() || dz > -MIN_EXTRUDE_DZ) {
+        return Err(Error::InvalidExtrudeDirection(config.extrude_direction));
+    }
+    let shear = [dx / dz.abs(), dy / dz.abs()];
+    let reshaped = config.extrude.is_some()
+        && (shear != [0., 0.] || config.taper != 0. || config.twist_degrees != 0.
+            || config.profile.is_some());
+
+    // the front z of each contour, and of the regions they bound
+    let contour_depth = config.contour_depth.as_deref().filter(|_| config.extrude.is_some());
+    let front_z = |contour: usize| match contour_depth.and_then(|depths| depths.get(contour)) {
+        Some(depth) => depth - z,
+        None => z,
+    };
+
+    let Tessellator{fill, stroke, buffers: bufs, outline, normals, uvs, edges} = tess;
+    bufs.vertices.clear();
+    bufs.indices.clear();
+
+    let regions = || match contour_depth {
+        Some(_) => regions(&builder.contours),
+        None => vec![(0, (0 .. builder.contours.len()).collect())],
+    };
+
+    match (config.stroke, config.cap_triangulation) {
+        (Some(StrokeConfig{width, join}), _) => {
+            let opts = lt::StrokeOptions::default()
+                .with_line_width(width)
+                .with_line_join(match join {
+                    StrokeJoin::Miter => lt::LineJoin::MiterClip,
+                    StrokeJoin::Round => lt::LineJoin::Round,
+                    StrokeJoin::Bevel => lt::LineJoin::Bevel,
+                })
+                .with_miter_limit(config.miter_limit.max(1.))
+                .with_tolerance(config.tolerance);
+
+            for (outer, members) in regions() {
+                let z = front_z(outer);
+                let path = path_events(members.iter().map(|&i| &builder.contours[i]));
+                let mut buf_builder = lt::BuffersBuilder::new(
+                    &mut *bufs,
+                    |v: lt::StrokeVertex<'_, '_>| -> [f32; 3] {
+                        let [x, y]: [f32; 2] = v.position().into();
+                        [x, y, z]
+                    }
+                );
+                stroke.tessellate(path, &opts, &mut buf_builder)
+                    .map_err(Error::Tessellation)?;
+            }
+
+            // the stroker winds triangles either way, so wind them all like the fill tessellator
+            let vertices = &bufs.vertices;
+            for [a, b, c] in bufs.indices.as_chunks_mut().0 {
+                let [pa, pb, pc] = [*a, *b, *c].map(|i| vertices[i as usize]);
+                if cross(sub(pb, pa), sub(pc, pa))[2] > 0. { std::mem::swap(b, c); }
+            }
+
+            // the stroke reaches beyond the outline
+            let cap = BoundingBox::from_points(&bufs.vertices);
+            [x_min, y_min, x_max, y_max] = [cap.mins[0], cap.mins[1], cap.maxs[0], cap.maxs[1]];
+        }
+
+        (None, CapTriangulation::Tessellated) => {
+            let opts = lt::FillOptions::default()
+                .with_fill_rule(match config.fill_rule {
+                    FillRule::NonZero => lt::FillRule::NonZero,
+                    FillRule::EvenOdd => lt::FillRule::EvenOdd,
+                })
+                .with_tolerance(config.tolerance);
+
+            for (outer, members) in regions() {
+                let z = front_z(outer);
+                let path = path_events(members.iter().map(|&i| &builder.contours[i]));
+                let mut buf_builder = lt::BuffersBuilder::new(
+                    &mut *bufs,
+                    |v: lt::FillVertex<'_>| -> [f32; 3] {
+                        let [x, y]: [f32; 2] = v.position().into();
+                        [x, y, z]
+                    }
+                );
+                fill.tessellate(path, &opts, &mut buf_builder)
+                    .map_err(Error::Tessellation)?;
+            }
+        }
+
+        (None, CapTriangulation::FanFromCentroid) => {
+            for (i, contour) in builder.contours.iter().enumerate().filter(|(_, c)| c.len() > 2) {
+                fan_from_centroid(contour, front_z(i), bufs);
+            }
+        }
+    }
+
+    let max_z = match contour_depth {
+        Some(_) => bufs.vertices.iter().map(|v| v[2]).fold(-z, f32::max),
+        None => z,
+    };
+    let bbox = BoundingBox::new([x_min, y_min, -z], [x_max, y_max, max_z]);
+
+    // the tessellator winds clockwise seen from +Z, so flip the front cap to face +Z
+    for [a, _, c] in bufs.indices.as_chunks_mut().0 {
+        std::mem::swap(a, c);
+    }
+
+    let i_front_end = bufs.indices.len();
+    let v_front_end = bufs.vertices.len();
+    let r = v_front_end as u32;
+
+    // moves a point at some depth below the front cap to where tapering, twisting and shearing
+    // put it
+    let [cx, cy, _] = surface_centroid(&bufs.vertices, &bufs.indices)
+        .unwrap_or([(x_min + x_max) / 2., (y_min + y_max) / 2., 0.]);
+    let displaced = |[x, y, z_at]: [f32; 3]| {
+        let below = z - z_at;
+        let t = if z > 0. {below / (2. * z)} else {0.};
+        let scale = 1. - config.taper * t;
+        let (sin, cos) = (config.twist_degrees.to_radians() * t).sin_cos();
+        let [rx, ry] = [x - cx, y - cy];
+        [
+            cx + (rx * cos - ry * sin) * scale + below * shear[0],
+            cy + (rx * sin + ry * cos) * scale + below * shear[1],
+            z_at,
+        ]
+    };
+
+    // the front cap as laid out by the outline, before any bevel or profile moves it
+    outline.clear();
+    outline.extend_from_slice(&bufs.vertices);
+    let outline = &*outline;
+    let loops = match config.extrude {
+        Some(_) => boundary_loops(&boundary_edges(&bufs.indices, edges)),
+        None => Vec::new(),
+    };
+
+    let mut i_rear_end = i_front_end;
+    if config.extrude.is_some() {
+        // add rear face
+        bufs.vertices.extend_from_within(..);
+        for v in &mut bufs.vertices[v_front_end..] { *v = displaced([v[0], v[1], -z]); }
+
+        let i_rear_base = bufs.indices.len();
+        bufs.indices.extend_from_within(..);
+        for [a, b, c] in bufs.indices[i_rear_base..].as_chunks_mut().0 {
+            [*a, *b, *c] = [*c + r, *b + r, *a + r];
+        }
+        i_rear_end = bufs.indices.len();
+    }
+
+    // the rings that the side walls pass through on the way from the front cap to the rear cap,
+    // as (depth below the front cap, inset) pairs; the rear cap itself is the final ring
+    let rings = side_rings(config, 2. * z);
+    let inward = inward_offsets(&loops, outline);
+    let ring_pos = |k: usize, v: u32| -> [f32; 3] {
+        let [x, y, front_z] = outline[v as usize];
+        displaced(match rings.get(k) {
+            Some(&(depth, inset)) => {
+                let [dx, dy] = inward[v as usize];
+                [x + dx * inset, y + dy * inset, (front_z - depth).max(-z)]
+            }
+            None => [x, y, -z],
+        })
+    };
+
+    if config.extrude.is_some() {
+        for v in 0 .. r { bufs.vertices[v as usize] = ring_pos(0, v); }
+    }
+
+    normals.clear();
+    if config.generate_normals {
+        normals.extend((0 .. bufs.vertices.len())
+            .map(|i| if i < v_front_end {[0., 0., 1.]} else {[0., 0., -1.]}));
+    }
+
+    uvs.clear();
+    if let Some(mapping) = config.uv_mapping {
+        uvs.extend(bufs.vertices.iter()
+            .map(|&v| match mapping {
+                UvMapping::Raw => [v[0], v[1]],
+                UvMapping::GlyphBounds | UvMapping::TextBounds => planar_uv(v, &bbox),
+            }));
+    }
+
+    // add sides, facing away from the cap interior, which lies to the left of each edge
+    if config.generate_normals || config.uv_mapping.is_some() {
+        for boundary in &loops {
+            let boundary = &seamed(boundary, config.uv_seam, outline);
+            let lengths: Vec<f32> = boundary.iter()
+                .map(|&(a, b)| length(sub(outline[b as usize], outline[a as usize])))
+                .collect();
+            let perimeter: f32 = lengths.iter().sum();
+
+            // the quads along each edge, from the front cap down, with their face normals
+            let quads: Vec<Vec<[[f32; 3]; 4]>> = boundary.iter()
+                .map(|&(a, b)| (0 .. rings.len())
+                    .map(|k| [ring_pos(k+1, a), ring_pos(k+1, b), ring_pos(k, b), ring_pos(k, a)])
+                    .collect())
+                .collect();
+            let faces: Vec<Vec<[f32; 3]>> = quads.iter()
+                .map(|edge| edge.iter()
+                    .map(|q| normalize(cross(sub(q[2], q[0]), sub(q[3], q[1]))))
+                    .collect())
+                .collect();
+            let closed = boundary.first().map(|e| e.0) == boundary.last().map(|e| e.1);
+            let min_cos = config.crease_angle.to_radians().cos();
+
+            let mut arc = 0.;
+            for (j, (&(a, b), len)) in boundary.iter().zip(lengths).enumerate() {
+                for (k, &quad) in quads[j].iter().enumerate() {
+                    let base = bufs.vertices.len() as u32;
+                    bufs.vertices.extend(quad);
+                    bufs.indices.extend([base, base+1, base+2, base, base+2, base+3]);
+
+                    if config.generate_normals && config.crease_angle > 0. {
+                        let corners = [(false, true), (true, true), (true, false), (false, false)];
+                        normals.extend(corners.map(|(end, lower)| {
+                            corner_normal(&faces, closed, [j, k], end, lower, min_cos)
+                        }));
+                    }
+                    else if config.generate_normals {
+                        normals.extend([faces[j][k]; 4]);
+                    }
+
+                    if let Some(mapping) = config.uv_mapping {
+                        let (u0, u1) = (arc, arc + len);
+                        let heights = [b, b, b, a].map(|v| outline[v as usize][2] + z);
+                        uvs.extend((0..4).map(|i| {
+                            let u = if i == 0 || i == 3 {u0} else {u1};
+                            let v = quad[i][2] + z;
+                            match mapping {
+                                UvMapping::Raw => [u, v],
+                                _ => [u / perimeter, if heights[i] > 0. {v / heights[i]} else {0.}],
+                            }
+                        }));
+                    }
+                }
+
+                arc += len;
+            }
+        }
+    }
+    else {
+        // share vertices between neighbouring quads; the first ring is the front cap, and the
+        // last the rear cap
+        let inner_rings: Vec<Vec<u32>> = (1 .. rings.len())
+            .map(|k| {
+                let mut ids = vec![u32::MAX; v_front_end];
+                for &v in loops.iter().flatten().flat_map(|(a, b)| [a, b]) {
+                    if ids[v as usize] == u32::MAX {
+                        ids[v as usize] = bufs.vertices.len() as u32;
+                        bufs.vertices.push(ring_pos(k, v));
+                    }
+                }
+                ids
+            })
+            .collect();
+
+        let id = |k: usize, v: u32| match k {
+            0 => v,
+            k if k == rings.len() => v + r,
+            k => inner_rings[k-1][v as usize],
+        };
+
+        bufs.indices.extend(
+            loops.iter().flatten()
+                .flat_map(|&(a, b)| (0 .. rings.len()).map(move |k| (k, a, b)))
+                .flat_map(|(k, a, b)| [
+                    id(k+1, a), id(k+1, b), id(k, b),
+                    id(k+1, a), id(k, b), id(k, a),
+                ])
+        );
+    }
+
+    let submeshes = [
+        (SubmeshKind::Front, 0 .. i_front_end as u32),
+        (SubmeshKind::Back, i_front_end as u32 .. i_rear_end as u32),
+        (SubmeshKind::Sides, i_rear_end as u32 .. bufs.indices.len() as u32),
+    ];
+    let submeshes = submeshes.into_iter().filter(|(_, range)| !range.is_empty()).collect();
+
+    // the scratch buffers are kept for the next glyph, so the mesh gets copies of them
+    let bbox = if reshaped {BoundingBox::from_points(&bufs.vertices)} else {bbox};
+    let mesh = Mesh {
+        bbox,
+        indices: bufs.indices.clone(),
+        vertices: bufs.vertices.clone(),
+        normals: normals.clone(),
+        uvs: uvs.clone(),
+        submeshes,
+    };
+    Ok(if config.faces == FaceFlags::default() {mesh} else {mesh.with_faces(config.faces)})
+}
+
+/// Finds the edges of the triangles in `indices` that aren't shared with another triangle, in
+/// the direction they run around their triangle, pairing them up in the empty `edge_map`.
+fn boundary_edges(
+    indices: &[u32],
+    edge_map: &mut std::collections::HashMap<(u32, u32), (u32, u32)>,
+) -> Vec<(u32, u32)> {
+    indices
+        .as_chunks().0.iter().copied()
+        .flat_map(|[a, b, c]| [(a, b), (b, c), (c, a)])
+        .for_each(|(a, b)| {
+            let key = if b < a {(b, a)} else {(a, b)};
+            use std::collections::hash_map::Entry;
+            match edge_map.entry(key) {
+                Entry::Occupied(e) => { e.remove(); },
+                Entry::Vacant(e)   => { e.insert((a, b)); },
+            }
+        });
+
+    let mut edges: Vec<(u32, u32)> = edge_map.drain().map(|(_, edge)| edge).collect();
+    edges.sort_unstable();
+    edges
+}
+
+/// The longest distance, relative to the inset, that a vertex may move when the outline is inset,
+/// and the default [Config::miter_limit].
+const MITER_LIMIT: f32 = 4.;
+
+/// For each vertex on one of the counter-clockwise boundary `loops`, the offset that insets the
+/// outline by a unit distance, mitered at corners. Other vertices have a zero offset.
+fn inward_offsets(loops: &[Vec<(u32, u32)>], vertices: &[[f32; 3]]) -> Vec<[f32; 2]> {
+    let left_normal = |(a, b): (u32, u32)| {
+        let [dx, dy, _] = sub(vertices[b as usize], vertices[a as usize]);
+        let l = dx.hypot(dy);
+        if l > 0. {[-dy / l, dx / l]} else {[0., 0.]}
+    };
+
+    let mut offsets = vec![[0.; 2]; vertices.len()];
+    for boundary in loops {
+        let closed = boundary.first().map(|e| e.0) == boundary.last().map(|e| e.1);
+        for (i, &edge) in boundary.iter().enumerate() {
+            let n_in = left_normal(edge);
+            let next = boundary.get(i + 1).or(boundary.first().filter(|_| closed));
+            let n_out = next.map_or(n_in, |&e| left_normal(e));
+            offsets[edge.1 as usize] = miter(n_in, n_out, MITER_LIMIT);
+
+            if i == 0 && !closed { offsets[edge.0 as usize] = n_in; }
+        }
+    }
+
+    offsets
+}
+
+/// The offset of the corner between two edges with unit normals `n_in` and `n_out` that moves
+/// both edges a unit distance along their normals, limited to `limit`.
+fn miter(n_in: [f32; 2], n_out: [f32; 2], limit: f32) -> [f32; 2] {
+    let sum = [n_in[0] + n_out[0], n_in[1] + n_out[1]];
+    let l = sum[0].hypot(sum[1]);
+    let dir = if l > 1e-6 {[sum[0] / l, sum[1] / l]} else {n_in};
+    let cos = (dir[0] * n_in[0] + dir[1] * n_in[1]).max(1. / limit);
+    [dir[0] / cos, dir[1] / cos]
+}
+
+/// The most a twisted glyph turns by between neighbouring rings, in degrees.
+const TWIST_STEP: f32 = 5.;
+
+/// The least that the unit [Config::extrude_direction] may point along -Z, which shears the side
+/// walls by at most a thousand times the depth.
+const MIN_EXTRUDE_DZ: f32 = 1e-3;
+
+/// The rings between the front and rear caps, as (depth below the front cap, inset) pairs,
+/// excluding the rear cap, which lies `depth` below the front cap.
+fn side_rings(config: &Config, depth: f32) -> Vec<(f32, f32)> {
+    let rings = profile_rings(config);
+    let twist = config.twist_degrees.abs();
+    if twist == 0. || depth <= 0. { return rings; }
+
+    // split each span between rings, up to the rear cap, so that none turns by too much
+    let ends = rings.iter().copied().skip(1).chain([(depth, 0.)]);
+    let mut split = Vec::new();
+    for ((d0, inset0), (d1, inset1)) in rings.iter().copied().zip(ends) {
+        let n = ((d1 - d0).abs() / depth * twist / TWIST_STEP).ceil().max(1.) as usize;
+        split.extend((0 .. n).map(|i| {
+            let t = i as f32 / n as f32;
+            (d0 + (d1 - d0) * t, inset0 + (inset1 - inset0) * t)
+        }));
+    }
+    split
+}
+
+/// The rings of the bevel or profile, as for [side_rings].
+fn profile_rings(config: &Config) -> Vec<(f32, f32)> {
+    if let Some(profile) = config.profile.as_deref().filter(|profile| !profile.is_empty()) {
+        return profile.iter().map(|&(depth, offset)| (depth, -offset)).collect();
+    }
+
+    let Some(BevelConfig{width, segments}) = config.bevel else { return vec![(0., 0.)] };
+    let n = segments.max(1);
+    (0 ..= n)
+        .map(|i| {
+            let angle = i as f32 / n as f32 * std::f32::consts::FRAC_PI_2;
+            (width * (1. - angle.cos()), width * (1. - angle.sin()))
+        })
+        .collect()
+}
+
+/// The normal at a corner of the side wall quad `[j, k]`, for edge `j` of a boundary loop and
+/// ring `k`, given the face normals of the loop's quads. This averages the normals of the quads
+/// around the corner whose faces are within the crease angle of the quad's own, given by its
+/// cosine. `end` picks the corner at the end of the edge rather than its start, and `lower` the
+/// corner on the ring further from the front cap.
+fn corner_normal(
+    faces: &[Vec<[f32; 3]>],
+    closed: bool,
+    [j, k]: [usize; 2],
+    end: bool,
+    lower: bool,
+    min_cos: f32,
+) -> [f32; 3] {
+    let own = faces[j][k];
+    let n = faces.len();
+    let neighbour = match end {
+        true  => (j + 1 < n || closed).then(|| (j + 1) % n),
+        false => (j > 0 || closed).then(|| (j + n - 1) % n),
+    };
+    let level = if lower {k + 1} else {k};
+
+    let sum = [Some(j), neighbour].into_iter().flatten()
+        .flat_map(|edge| [level.checked_sub(1), Some(level)].map(|ring| ring.map(|k| (edge, k))))
+        .flatten()
+        .filter_map(|(edge, ring)| faces[edge].get(ring).copied())
+        .filter(|face| face[0] * own[0] + face[1] * own[1] + face[2] * own[2] >= min_cos)
+        .fold([0.; 3], add);
+    normalize(sum)
+}
+
+/// The closed loop `boundary` rotated to start where `seam` puts the seam, or an open one as it
+/// is.
+fn seamed(boundary: &[(u32, u32)], seam: UvSeam, vertices: &[[f32; 3]]) -> Vec<(u32, u32)> {
+    let mut boundary = boundary.to_vec();
+    let closed = boundary.first().map(|e| e.0) == boundary.last().map(|e| e.1);
+    if let (UvSeam::AtPoint([x, y]), true) = (seam, closed) {
+        let distance = |&(v, _): &(u32, u32)| {
+            let [vx, vy, _] = vertices[v as usize];
+            (vx - x).hypot(vy - y)
+        };
+        let start = (0 .. boundary.len())
+            .min_by(|&i, &j| distance(&boundary[i]).total_cmp(&distance(&boundary[j])))
+            .unwrap_or(0);
+        boundary.rotate_left(start);
+    }
+    boundary
+}
+
+/// Chains directed boundary edges into loops around the cap.
+fn boundary_loops(edges: &[(u32, u32)]) -> Vec<Vec<(u32, u32)>> {
+    let mut outgoing: std::collections::HashMap<u32, Vec<usize>> = Default::default();
+    for (i, &(a, _)) in edges.iter().enumerate() {
+        outgoing.entry(a).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut loops = Vec::new();
+    for start in 0 .. edges.len() {
+        if used[start] { continue; }
+
+        let mut boundary = Vec::new();
+        let mut next = Some(start);
+        while let Some(e) = next {
+            used[e] = true;
+            boundary.push(edges[e]);
+            next = outgoing.get(&edges[e].1)
+                .and_then(|es| es.iter().copied().find(|&n| !used[n]));
+        }
+        loops.push(boundary);
+    }
+
+    loops
+}
+
+/// Triangulates `contour` as a fan around its centroid, with the same winding as the tessellator
+/// produces.
+fn fan_from_centroid(
+    contour: &[lt::math::Point],
+    z: f32,
+    bufs: &mut lt::VertexBuffers<[f32; 3], u32>,
+) {
+    let area = signed_area(contour);
+    let centroid = if area != 0. {
+        contour.iter()
+            .zip(contour.iter().cycle().skip(1))
+            .map(|(a, b)| (a.to_vector() + b.to_vector()) * (a.x * b.y - b.x * a.y))
+            .sum::<lt::math::Vector>() / (3. * area)
+    }
+    else {
+        contour.iter().map(|p| p.to_vector()).sum::<lt::math::Vector>() / contour.len() as f32
+    };
+
+    let c = bufs.vertices.len() as u32;
+    bufs.vertices.push([centroid.x, centroid.y, z]);
+    bufs.vertices.extend(contour.iter().map(|p| [p.x, p.y, z]));
+
+    let n = contour.len() as u32;
+    bufs.indices.extend((0..n).flat_map(|i| {
+        let (a, b) = (c + 1 + i, c + 1 + (i + 1) % n);
+        if area > 0. {[c, b, a]} else {[c, a, b]}
+    }));
+}
+
+/// Accumulates a glyph outline to be meshed by [build_mesh_from_outline_builder].
+///
+/// Curves are flattened into line segments as they are added. Coordinates are multiplied by the
+/// scale given to [OutlineBuilder::new].
+///
+/// It implements [ttf_parser::OutlineBuilder], so a face can outline a glyph straight into it
+/// with `Face::outline_glyph`; outlines from other sources are added with its own methods.
+#[derive(Clone)]
+pub struct OutlineBuilder {
+    contours: Vec<Contour>,
+    scale: f32,
+    tolerance: f32,
+    bbox: Option<[f32; 4]>,
+}
+
+/// A closed polyline. The closing edge from the last point back to the first is implicit.
+type Contour = Vec<lt::math::Point>;
+
+impl OutlineBuilder {
+    /// Creates a new, empty [OutlineBuilder].
+    ///
+    /// Arguments:
+    /// * `scale`: The factor applied to every incoming coordinate.
+    /// * `tolerance`: The maximum distance between a curve and its flattened approximation, in
+    ///   scaled units, as for [Config::tolerance].
+    pub fn new(scale: f32, tolerance: f32) -> Self {
+        Self{contours: Vec::new(), scale, tolerance, bbox: None}
+    }
+
+    fn point(&mut self, x: f32, y: f32) -> lt::math::Point {
+        let (x, y) = (x * self.scale, y * self.scale);
+        let bbox = self.bbox.get_or_insert([x, y, x, y]);
+        *bbox = [bbox[0].min(x), bbox[1].min(y), bbox[2].max(x), bbox[3].max(y)];
+        lt::math::point(x, y)
+    }
+
+    fn last(&self) -> lt::math::Point {
+        self.contours.last()
+            .and_then(|c| c.last().copied())
+            .unwrap_or_default()
+    }
+
+    fn push(&mut self, p: lt::math::Point) {
+        match self.contours.last_mut() {
+            Some(c) if c.last() != Some(&p) => c.push(p),
+            Some(_) => { },
+            None => self.contours.push(vec![p]),
+        }
+    }
+
+    /// Starts a new contour at the given point.
+    pub fn move_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.contours.push(vec![p]);
+    }
+
+    /// Adds a straight line to the current contour.
+    pub fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.push(p);
+    }
+
+    /// Adds a quadratic bézier curve to the current contour.
+    pub fn quad_to(&mut self, xc: f32, yc: f32, x: f32, y: f32) {
+        let (from, ctrl, to) = (self.last(), self.point(xc, yc), self.point(x, y));
+        lt::geom::QuadraticBezierSegment{from, ctrl, to}
+            .for_each_flattened(self.tolerance, &mut |seg| self.push(seg.to));
+    }
+
+    /// Adds a cubic bézier curve to the current contour.
+    pub fn curve_to(&mut self, xc0: f32, yc0: f32, xc1: f32, yc1: f32, x: f32, y: f32) {
+        let (from, ctrl1, ctrl2, to)
+            = (self.last(), self.point(xc0, yc0), self.point(xc1, yc1), self.point(x, y));
+        lt::geom::CubicBezierSegment{from, ctrl1, ctrl2, to}
+            .for_each_flattened(self.tolerance, &mut |seg| self.push(seg.to));
+    }
+
+    /// Closes the current contour.
+    pub fn close(&mut self) {
+        if let Some(c) = self.contours.last_mut() {
+            if c.len() > 1 && c.first() == c.last() { c.pop(); }
+        }
+    }
+
+    /// Applies the adjustments to the outline set in `config`, ahead of tessellation.
+    fn adjust(&mut self, config: &Config) {
+        if config.normalize_winding || config.fill_rule == FillRule::EvenOdd {
+            normalize_winding(&mut self.contours);
+        }
+        if config.embolden != 0. {
+            self.embolden(config.embolden, config.embolden_join, config.miter_limit.max(1.));
+            if config.contour_depth.is_none() { self.merge_overlaps(); }
+        }
+        if config.slant != 0. { self.slant(config.slant); }
+    }
+
+    /// Offsets every contour by `distance` away from the region it fills, shaping the corners
+    /// where the offset edges part with `join`, and mitering the rest, up to `miter_limit`.
+    fn embolden(&mut self, distance: f32, join: StrokeJoin, miter_limit: f32) {
+        let levels = nesting_levels(&self.contours);
+        let radius = distance.abs();
+        let tolerance = self.tolerance;
+        for (contour, level) in self.contours.iter_mut().zip(levels).filter(|(c, _)| c.len() > 2) {
+            // outer contours fill their inside and holes their outside, whichever way they wind;
+            // normals point the way the contour moves
+            let outward = if (signed_area(contour) > 0.) == (level % 2 == 0) {1.} else {-1.};
+            let outward = outward * distance.signum();
+            let right_normal = |a: lt::math::Point, b: lt::math::Point| {
+                let d = b - a;
+                let l = d.length();
+                if l > 0. {[outward * d.y / l, -outward * d.x / l]} else {[0., 0.]}
+            };
+
+            let n = contour.len();
+            let mut offset = Vec::with_capacity(n);
+            // the first and last offset points of each corner
+            let mut corners = Vec::with_capacity(n);
+            for i in 0 .. n {
+                let first = offset.len();
+                let [prev, p, next] = [(i + n - 1) % n, i, (i + 1) % n].map(|j| contour[j]);
+                let (n_in, n_out) = (right_normal(prev, p), right_normal(p, next));
+                let at = |[dx, dy]: [f32; 2]| p + lt::math::vector(dx, dy) * radius;
+
+                // the offset edges part where the next edge turns away from the offset
+                let parting = n_in[0] * (next.x - p.x) + n_in[1] * (next.y - p.y) < 0.;
+                let [mx, my] = miter(n_in, n_out, f32::INFINITY);
+                match join {
+                    _ if !parting => offset.push(at(miter(n_in, n_out, miter_limit))),
+                    StrokeJoin::Miter if mx.hypot(my) <= miter_limit => offset.push(at([mx, my])),
+                    StrokeJoin::Miter | StrokeJoin::Bevel => offset.extend([at(n_in), at(n_out)]),
+                    StrokeJoin::Round => {
+                        let cross = n_in[0] * n_out[1] - n_in[1] * n_out[0];
+                        let angle = cross.atan2(n_in[0] * n_out[0] + n_in[1] * n_out[1]);
+                        let step = 2. * (1. - (tolerance / radius).min(1.)).acos();
+                        let steps = (angle.abs() / step.max(0.01)).ceil().max(1.) as usize;
+                        offset.extend((0 ..= steps).map(|k| {
+                            let (sin, cos) = (angle * k as f32 / steps as f32).sin_cos();
+                            at([n_in[0] * cos - n_in[1] * sin, n_in[0] * sin + n_in[1] * cos])
+                        }));
+                    }
+                }
+                corners.push([first, offset.len() - 1]);
+            }
+
+            // a contour whose every edge has turned back, such as a hole offset past its middle,
+            // has closed up
+            let closed_up = (0 .. n).all(|i| {
+                let j = (i + 1) % n;
+                (offset[corners[j][0]] - offset[corners[i][1]]).dot(contour[j] - contour[i]) <= 0.
+            });
+            *contour = if closed_up {Vec::new()} else {offset};
+        }
+
+        self.update_bbox();
+    }
+
+    /// Replaces the contours with the outline of the region they fill by the nonzero rule, so
+    /// that overlapping contours are merged and none of them cross. Outer contours wind
+    /// counter-clockwise and holes clockwise, as [normalize_winding] leaves them.
+    fn merge_overlaps(&mut self) {
+        let mut buffers: lt::VertexBuffers<lt::math::Point, u32> = lt::VertexBuffers::new();
+        let opts = lt::FillOptions::default().with_fill_rule(lt::FillRule::NonZero);
+        let mut builder = lt::BuffersBuilder::new(&mut buffers, |v: lt::FillVertex<'_>| {
+            v.position()
+        });
+        let filled = lt::FillTessellator::new()
+            .tessellate(path_events(&self.contours), &opts, &mut builder);
+        // if the outline can't be filled, it can't be meshed either, so it's left as it is
+        if filled.is_err() { return; }
+
+        // the tessellator winds triangles clockwise, so their edges run clockwise around the
+        // filled region, and backwards around holes
+        let edges = boundary_edges(&buffers.indices, &mut Default::default());
+        self.contours = boundary_loops(&edges).into_iter()
+            .map(|boundary| {
+                boundary.iter().rev().map(|&(_, b)| buffers.vertices[b as usize]).collect()
+            })
+            .collect();
+    }
+
+    /// Shears every contour horizontally by `slant` times the height above the baseline.
+    fn slant(&mut self, slant: f32) {
+        for p in self.contours.iter_mut().flatten() { p.x += slant * p.y; }
+        self.update_bbox();
+    }
+
+    /// Recomputes the bounding box after the contours have been moved.
+    fn update_bbox(&mut self) {
+        self.bbox = self.contours.iter().flatten().fold(None, |bbox, p| {
+            let [x0, y0, x1, y1] = bbox.unwrap_or([p.x, p.y, p.x, p.y]);
+            Some([x0.min(p.x), y0.min(p.y), x1.max(p.x), y1.max(p.y)])
+        });
+    }
+}
+
+/// The path events of the closed `contours` with more than two points, which the tessellators
+/// take without a path being built.
+fn path_events<'a>(contours: impl IntoIterator<Item = &'a Contour> + 'a)
+    -> impl Iterator<Item = ltp::PathEvent> + 'a
+{
+    contours.into_iter().filter(|c| c.len() > 2).flat_map(|contour| {
+        let (first, last) = (contour[0], contour[contour.len() - 1]);
+        std::iter::once(ltp::PathEvent::Begin{at: first})
+            .chain(contour.windows(2).map(|w| ltp::PathEvent::Line{from: w[0], to: w[1]}))
+            .chain(std::iter::once(ltp::PathEvent::End{last, first, close: true}))
+    })
+}
+
+/// Twice the signed area of `contour`; positive if it winds counter-clockwise.
+fn signed_area(contour: &[lt::math::Point]) -> f32 {
+    contour.iter()
+        .zip(contour.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum()
+}
+
+/// Whether `p` lies inside `contour`, by the even-odd rule.
+fn contains(contour: &[lt::math::Point], p: lt::math::Point) -> bool {
+    contour.iter()
+        .zip(contour.iter().cycle().skip(1))
+        .filter(|(a, b)| (a.y > p.y) != (b.y > p.y)
+            && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x))
+        .count() % 2 == 1
+}
+
+/// For each contour, the number of other contours it lies inside.
+fn nesting_levels(contours: &[Contour]) -> Vec<usize> {
+    contours.iter().enumerate()
+        .map(|(i, contour)| {
+            let Some(&p) = contour.first() else { return 0 };
+            contours.iter().enumerate()
+                .filter(|&(j, other)| j != i && contains(other, p))
+                .count()
+        })
+        .collect()
+}
+
+/// Groups contours into filled regions, each made up of an outer contour and the holes directly
+/// inside it. Returns the index of each region's outer contour alongside the indices of all its
+/// contours.
+fn regions(contours: &[Contour]) -> Vec<(usize, Vec<usize>)> {
+    let levels = nesting_levels(contours);
+    let mut regions: Vec<(usize, Vec<usize>)> = levels.iter().enumerate()
+        .filter(|(_, &level)| level % 2 == 0)
+        .map(|(i, _)| (i, vec![i]))
+        .collect();
+
+    for (hole, &level) in levels.iter().enumerate().filter(|(_, &level)| level % 2 == 1) {
+        let p = contours[hole][0];
+        let parent = regions.iter_mut()
+            .find(|(outer, _)| levels[*outer] == level - 1 && contains(&contours[*outer], p));
+        if let Some((_, members)) = parent { members.push(hole); }
+    }
+
+    regions
+}
+
+/// Reorients contours so that outer contours wind counter-clockwise and holes clockwise, based on
+/// how deeply each contour is nested inside the others.
+fn normalize_winding(contours: &mut [Contour]) {
+    let flips: Vec<bool> = contours.iter()
+        .zip(nesting_levels(contours))
+        .map(|(contour, level)| (signed_area(contour) > 0.) != (level % 2 == 0))
+        .collect();
+
+    for (contour, flip) in contours.iter_mut().zip(flips) {
+        if flip { contour.reverse(); }
+    }
+}
+
+impl ttf_parser::OutlineBuilder for OutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) { self.move_to(x, y); }
+    fn line_to(&mut self, x: f32, y: f32) { self.line_to(x, y); }
+    fn close(&mut self) { self.close(); }
+
+    fn quad_to(&mut self, xc: f32, yc: f32, x: f32, y: f32) {
+        self.quad_to(xc, yc, x, y);
+    }
+
+    fn curve_to(&mut self, xc0: f32, yc0: f32, xc1: f32, yc1: f32, x: f32, y: f32) {
+        self.curve_to(xc0, yc0, xc1, yc1, x, y);
+    }
+}
+
+/// The face of the [test font](test_font::test_font) that most tests mesh glyphs from, built
+/// once.
+#[cfg(test)]
+fn test_face() -> ttf_parser::Face<'static> {
+    static DATA: std::sync::OnceLock<Vec<u8>> = std::sync::OnceLock::new();
+    ttf_parser::Face::parse(DATA.get_or_init(test_font::test_font), 0).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The triangles of `mesh` by the bits of their positions, each rotated to start at its
+    /// least corner so that the winding is kept, in sorted order.
+    fn triangles(mesh: &Mesh) -> Vec<[[u32; 3]; 3]> {
+        let mut triangles: Vec<_> = mesh.indices.as_chunks::<3>().0.iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| mesh.vertices[i as usize].map(f32::to_bits));
+                let least = [a, b, c].into_iter().min().unwrap();
+                if least == a {[a, b, c]} else if least == b {[b, c, a]} else {[c, a, b]}
+            })
+            .collect();
+        triangles.sort_unstable();
+        triangles
+    }
+
+    #[test]
+    fn split_mesh_recombines() {
+        let face = test_face();
+        let generator = MeshGenerator::new(&face);
+        let glyph = face.glyph_index('B').unwrap();
+        let mesh = generator.generate_mesh(glyph).unwrap();
+        let SplitMesh{front, rear, sides} = generator.generate_mesh_split(glyph).unwrap();
+        assert!([&front, &rear, &sides].iter().all(|part| !part.indices.is_empty()));
+
+        let mut combined = Mesh::default();
+        for part in [&front, &rear, &sides] { combined.append_translated(part, [0.; 3]); }
+        assert_eq!(triangles(&combined), triangles(&mesh));
+    }
+
+    #[test]
+    fn centroid_follows_ink() {
+        let face = test_face();
+        let generator = MeshGenerator::new(&face);
+        let mesh = generator.generate_mesh(face.glyph_index('L').unwrap()).unwrap();
+        let [cx, cy, cz] = mesh.centroid();
+        let [bx, by, _] = mesh.bbox.center();
+
+        // most of an L's ink is in its stem, left of and below the middle of its box
+        assert!(cx < bx - 0.05, "centroid x {cx} is not left of {bx}");
+        assert!(cy < by, "centroid y {cy} is not below {by}");
+        assert!(cz.abs() < 1e-4);
+    }
+
+    #[test]
+    fn normalize_winding_cuts_holes() {
+        // a square with a square hole, both wound clockwise as in fonts with uniform winding
+        let mut builder = OutlineBuilder::new(1., 0.1);
+        for [x0, y0, x1, y1] in [[0., 0., 3., 3.], [1., 1., 2., 2.]] {
+            builder.move_to(x0, y0);
+            for (x, y) in [(x0, y1), (x1, y1), (x1, y0)] { builder.line_to(x, y); }
+            builder.close();
+        }
+        let config = Config{normalize_winding: true, ..Default::default()};
+        let mesh = build_mesh_from_outline_builder(builder, &config).unwrap();
+
+        for (kind, sign) in [(SubmeshKind::Front, 1.), (SubmeshKind::Back, -1.)] {
+            let mut area = 0.;
+            for t in mesh.indices[mesh.part(kind)].as_chunks::<3>().0 {
+                let [a, b, c] = t.map(|i| mesh.vertices[i as usize]);
+                let normal = cross(sub(b, a), sub(c, a));
+                assert!(normal[2] * sign > 0., "{kind:?} triangle {t:?} faces the wrong way");
+                area += normal[2].abs() / 2.;
+            }
+            assert!((area - 8f32).abs() < 1e-4, "{kind:?} covers {area}, not 8");
+        }
+    }
+
+    #[test]
+    fn embolden_cuts_off_sharp_corners() {
+        // a tall, thin triangle, whose apex is about 11° wide
+        let height_with = |embolden_join, miter_limit| {
+            let mut builder = OutlineBuilder::new(1., 0.01);
+            builder.move_to(0., 0.);
+            for (x, y) in [(1., 0.), (0.5, 5.)] { builder.line_to(x, y); }
+            builder.close();
+            let config = Config {
+                extrude: None,
+                embolden: 0.1,
+                embolden_join,
+                miter_limit,
+                ..Default::default()
+            };
+            build_mesh_from_outline_builder(builder, &config).unwrap().bbox.maxs[1]
+        };
+
+        // the miter reaches about ten times the offset past the apex
+        assert!(height_with(StrokeJoin::Miter, 20.) > 5.9);
+        for (join, limit) in [(StrokeJoin::Miter, 4.), (StrokeJoin::Bevel, 20.)] {
+            let height = height_with(join, limit);
+            assert!(height < 5.1, "{join:?} with limit {limit} reaches {height}");
+        }
+        let round = height_with(StrokeJoin::Round, 20.);
+        assert!((5.09 .. 5.11).contains(&round), "the round join reaches {round}");
+    }
+
+    #[test]
+    fn embolden_merges_overlaps() {
+        let area = |mesh: Mesh| mesh.indices.as_chunks::<3>().0.iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| mesh.vertices[i as usize]);
+                cross(sub(b, a), sub(c, a))[2] / 2.
+            })
+            .sum::<f32>();
+
+        // a cross of two bars wound alike, whose middle even-odd would leave empty
+        let cross_with = |fill_rule| {
+            let mut builder = OutlineBuilder::new(1., 0.01);
+            let bars = [
+                [(0., 1.), (3., 1.), (3., 2.), (0., 2.)],
+                [(1., 0.), (2., 0.), (2., 3.), (1., 3.)],
+            ];
+            for bar in bars {
+                builder.move_to(bar[0].0, bar[0].1);
+                for (x, y) in &bar[1 ..] { builder.line_to(*x, *y); }
+                builder.close();
+            }
+            let config = Config{extrude: None, embolden: 0.1, fill_rule, ..Default::default()};
+            area(build_mesh_from_outline_builder(builder, &config).unwrap())
+        };
+        for fill_rule in [FillRule::NonZero, FillRule::EvenOdd] {
+            let area = cross_with(fill_rule);
+            assert!((area - (2. * 3.2 * 1.2 - 1.2 * 1.2)).abs() < 1e-4, "{fill_rule:?}: {area}");
+        }
+
+        // the o's ring, of radius 0.25 around a hole of radius 0.15, keeps its hole while it's
+        // open, and loses it once it closes up
+        let face = test_face();
+        let disk = |r: f32| std::f32::consts::PI * r * r;
+        for fill_rule in [FillRule::NonZero, FillRule::EvenOdd] {
+            let o_with = |embolden| {
+                let config = Config{tolerance: 0.002, extrude: None, embolden, fill_rule,
+                    ..Default::default()};
+                let generator = MeshGenerator::new_with_config(&face, config);
+                area(generator.generate_mesh(face.glyph_index('o').unwrap()).unwrap())
+            };
+            let open = o_with(0.05);
+            assert!((open / (disk(0.3) - disk(0.1)) - 1.).abs() < 0.01, "the ring covers {open}");
+            let closed = o_with(0.2);
+            assert!((closed / disk(0.45) - 1.).abs() < 0.01, "the disk covers {closed}");
+        }
+    }
+
+    /// Asserts that every normal of `mesh` has unit length, and points the same way as the
+    /// faces of the triangles using it.
+    fn assert_normals_match_faces(mesh: &Mesh) {
+        assert_eq!(mesh.normals.len(), mesh.vertices.len());
+        for n in &mesh.normals { assert!((length(*n) - 1.).abs() < 1e-5, "{n:?} isn't unit"); }
+        for t in mesh.indices.as_chunks::<3>().0 {
+            let [a, b, c] = t.map(|i| mesh.vertices[i as usize]);
+            let face = cross(sub(b, a), sub(c, a));
+            for i in t { assert!(dot(face, mesh.normals[*i as usize]) > 0., "{t:?} at {i}"); }
+        }
+    }
+
+    #[test]
+    fn normals_match_faces() {
+        let face = test_face();
+        let glyph = face.glyph_index('&').unwrap();
+
+        let generator = MeshGenerator::new(&face);
+        let flat = generator.generate_flat_double_sided(glyph).unwrap();
+        assert_normals_match_faces(&flat);
+
+        // the copies share positions, with opposite normals
+        let n = flat.vertices.len() / 2;
+        assert_eq!(flat.vertices[.. n], flat.vertices[n ..]);
+        assert!(flat.normals[.. n].iter().all(|&n| n == [0., 0., 1.]));
+        assert!(flat.normals[n ..].iter().all(|&n| n == [0., 0., -1.]));
+
+        for crease_angle in [0., 30.] {
+            let config = Config{generate_normals: true, crease_angle, ..Default::default()};
+            let generator = MeshGenerator::new_with_config(&face, config);
+            assert_normals_match_faces(&generator.generate_mesh(glyph).unwrap());
+        }
+    }
+
+    #[test]
+    fn uv_seam_at_point() {
+        let face = test_face();
+        let glyph = face.glyph_index('L').unwrap();
+        let mesh_with = |uv_seam| {
+            let uv_mapping = Some(UvMapping::GlyphBounds);
+            let config = Config{uv_mapping, uv_seam, ..Default::default()};
+            MeshGenerator::new_with_config(&face, config).generate_mesh(glyph).unwrap()
+        };
+
+        // the L's only contour wraps around at the bottom-right corner of its foot
+        let BoundingBox{mins, maxs} = mesh_with(UvSeam::Auto).bbox;
+        let corner = [maxs[0], mins[1]];
+        let mesh = mesh_with(UvSeam::AtPoint([corner[0] + 0.1, corner[1] - 0.1]));
+        let sides = mesh.part(SubmeshKind::Sides);
+        let seam: Vec<_> = mesh.indices[sides].iter()
+            .filter(|&&i| mesh.uvs[i as usize][0] == 0.)
+            .map(|&i| mesh.vertices[i as usize])
+            .collect();
+        assert!(!seam.is_empty());
+        for [x, y, _] in seam { assert_eq!([x, y], corner); }
+    }
+
+    #[test]
+    fn contour_depth_per_contour() {
+        let face = test_face();
+        let glyph = face.glyph_index('i').unwrap();
+        // the stem of the i is its first contour and the dot its second
+        let config = Config {
+            extrude: Some(1.),
+            contour_depth: Some([0.2, 0.6].into()),
+            ..Default::default()
+        };
+        let mesh = MeshGenerator::new_with_config(&face, config).generate_mesh(glyph).unwrap();
+
+        // each region's front cap is at its own depth, with the dot wholly above the stem
+        let (mut stem, mut dot) = (vec![], vec![]);
+        for &i in &mesh.indices[mesh.part(SubmeshKind::Front)] {
+            let [_, y, z] = mesh.vertices[i as usize];
+            if (z - (0.2 - 0.5)).abs() < 1e-5 {stem.push(y)}
+            else if (z - (0.6 - 0.5)).abs() < 1e-5 {dot.push(y)}
+            else {panic!("front cap vertex at z = {z}")}
+        }
+        let stem_top = stem.iter().copied().fold(f32::MIN, f32::max);
+        assert!(!dot.is_empty() && dot.iter().all(|&y| y > stem_top));
+        assert!((mesh.bbox.maxs[2] - 0.1).abs() < 1e-5);
+        for &i in &mesh.indices[mesh.part(SubmeshKind::Back)] {
+            assert_eq!(mesh.vertices[i as usize][2], -0.5);
+        }
+    }
+
+    #[test]
+    fn extrude_direction_must_point_back() {
+        let face = test_face();
+        let glyph = face.glyph_index('L').unwrap();
+        let mesh_with = |extrude, extrude_direction| {
+            let config = Config{extrude, extrude_direction, ..Default::default()};
+            MeshGenerator::new_with_config(&face, config).generate_mesh(glyph)
+        };
+
+        assert!(mesh_with(Some(1.), [0.5, 0., -1.]).is_ok());
+        let invalid = [[1., 0., 0.], [1., 0., -1e-5], [0., 0., 1.], [0.; 3], [0., 0., f32::NAN]];
+        for direction in invalid {
+            let result = mesh_with(Some(1.), direction);
+            assert!(matches!(result, Err(Error::InvalidExtrudeDirection(_))), "{direction:?}");
+        }
+        // flat meshes have no direction to check
+        assert!(mesh_with(None, [1., 0., 0.]).is_ok());
+    }
+
+    #[test]
+    fn profile_steps_the_side_walls() {
+        let face = test_face();
+        let glyph = face.glyph_index('I').unwrap();
+        let mesh_with = |profile| {
+            let config = Config{extrude: Some(1.), profile, ..Default::default()};
+            MeshGenerator::new_with_config(&face, config).generate_mesh(glyph).unwrap()
+        };
+        let plain = mesh_with(None);
+
+        // a step out by 0.02 just below the front cap
+        let steps = vec![(0., 0.), (0.05, 0.), (0.05, 0.02), (1., 0.02)];
+        let stepped = mesh_with(Some(steps.into()));
+        let width = |mesh: &Mesh, range: std::ops::Range<usize>| {
+            let xs = mesh.indices[range].iter().map(|&i| mesh.vertices[i as usize][0]);
+            let (lo, hi) = xs.fold((f32::MAX, f32::MIN), |(lo, hi), x| (lo.min(x), hi.max(x)));
+            hi - lo
+        };
+        let plain_width = plain.bbox.size()[0];
+        let front = stepped.part(SubmeshKind::Front);
+        assert!((width(&stepped, front) - plain_width).abs() < 1e-5);
+        assert!((stepped.bbox.size()[0] - (plain_width + 0.04)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn scratch_buffers_are_reset_between_glyphs() {
+        let face = test_face();
+        let config = Config {
+            generate_normals: true,
+            uv_mapping: Some(UvMapping::GlyphBounds),
+            ..Default::default()
+        };
+        let glyphs: Vec<GlyphId> = "aB&a.".chars().map(|c| face.glyph_index(c).unwrap()).collect();
+        let fresh = |glyph| {
+            MeshGenerator::new_with_config(&face, config.clone()).generate_mesh(glyph).unwrap()
+        };
+
+        let mut generator = MeshGenerator::new_with_config(&face, config.clone());
+        let batch = generator.generate_meshes(&glyphs).unwrap();
+        for (&glyph, mesh) in glyphs.iter().zip(&batch) { assert_eq!(*mesh, fresh(glyph)); }
+
+        // meshing into a mesh of another glyph, in a generator that has meshed others
+        generator.clear_cache();
+        let mut mesh = batch[1].clone();
+        generator.generate_mesh_into(glyphs[2], &mut mesh).unwrap();
+        assert_eq!(mesh, fresh(glyphs[2]));
+    }
+}
@@ -0,0 +1,80 @@
+//! Generating chains of glyph meshes at several levels of detail.
+
+use crate::{build_mesh, Config, GlyphId, Mesh, MeshGenerator, OutlineBuilder, Result};
+
+/// One step of a glyph outline, in font units, before flattening.
+enum Segment {
+    MoveTo(f32, f32),
+    LineTo(f32, f32),
+    QuadTo(f32, f32, f32, f32),
+    CurveTo(f32, f32, f32, f32, f32, f32),
+    Close,
+}
+
+/// A glyph outline recorded once, to be flattened at several tolerances.
+#[derive(Default)]
+struct Recording(Vec<Segment>);
+
+impl ttf_parser::OutlineBuilder for Recording {
+    fn move_to(&mut self, x: f32, y: f32) { self.0.push(Segment::MoveTo(x, y)); }
+    fn line_to(&mut self, x: f32, y: f32) { self.0.push(Segment::LineTo(x, y)); }
+    fn close(&mut self) { self.0.push(Segment::Close); }
+
+    fn quad_to(&mut self, xc: f32, yc: f32, x: f32, y: f32) {
+        self.0.push(Segment::QuadTo(xc, yc, x, y));
+    }
+
+    fn curve_to(&mut self, xc0: f32, yc0: f32, xc1: f32, yc1: f32, x: f32, y: f32) {
+        self.0.push(Segment::CurveTo(xc0, yc0, xc1, yc1, x, y));
+    }
+}
+
+impl Recording {
+    fn replay(&self, builder: &mut OutlineBuilder) {
+        for segment in &self.0 {
+            match *segment {
+                Segment::MoveTo(x, y) => builder.move_to(x, y),
+                Segment::LineTo(x, y) => builder.line_to(x, y),
+                Segment::QuadTo(xc, yc, x, y) => builder.quad_to(xc, yc, x, y),
+                Segment::CurveTo(xc0, yc0, xc1, yc1, x, y)
+                    => builder.curve_to(xc0, yc0, xc1, yc1, x, y),
+                Segment::Close => builder.close(),
+            }
+        }
+    }
+}
+
+impl<'face> MeshGenerator<'face> {
+    /// Generates a chain of [Mesh]es for the given `glyph`, one for each tolerance, for engines
+    /// with discrete level of detail systems.
+    ///
+    /// The glyph's outline is read from the face once and flattened at each tolerance in turn,
+    /// in place of [Config::tolerance]; the rest of the [Config] applies to every level. Listing
+    /// the tolerances in increasing order gives levels with decreasing detail. The meshes aren't
+    /// cached. For a fixed fraction of the triangles instead, see [Mesh::simplify].
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    /// * `tolerances`: The tolerance of each level, in mesh units.
+    ///
+    /// Returns:
+    /// A [Result] containing a [Mesh] for each tolerance if successful, otherwise an
+    /// [Error](crate::Error).
+    pub fn generate_mesh_lods(&self, glyph: GlyphId, tolerances: &[f32]) -> Result<Vec<Mesh>> {
+        let mut recording = Recording::default();
+        if !self.outline_into(glyph, &mut recording) {
+            return Ok(tolerances.iter().map(|_| Mesh::default()).collect());
+        }
+
+        let tess = &mut self.tessellator.borrow_mut();
+        tolerances.iter()
+            .map(|&tolerance| {
+                let config = Config{tolerance, ..self.config.clone()};
+                let mut builder = OutlineBuilder::new(self.scale, tolerance);
+                recording.replay(&mut builder);
+                let mesh = build_mesh(builder, &config, tess)?;
+                Ok(mesh.in_coordinates(config.coordinates))
+            })
+            .collect()
+    }
+}
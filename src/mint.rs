@@ -0,0 +1,46 @@
+//! Interop with the math libraries that accept `mint` types.
+
+use {
+    crate::{BoundingBox, BoundingSphere, Mesh},
+    mint::{Point3, Vector3},
+};
+
+impl Mesh {
+    /// Get the positions of this mesh's vertices as `mint` points.
+    pub fn mint_vertices(&self) -> impl ExactSizeIterator<Item = Point3<f32>> + '_ {
+        self.vertices.iter().map(|&v| v.into())
+    }
+
+    /// Get the normals of this mesh's vertices as `mint` vectors, if they were generated.
+    pub fn mint_normals(&self) -> impl ExactSizeIterator<Item = Vector3<f32>> + '_ {
+        self.normals.iter().map(|&n| n.into())
+    }
+}
+
+/// Converts the minimum and maximum points of a box.
+impl From<[Point3<f32>; 2]> for BoundingBox {
+    fn from([mins, maxs]: [Point3<f32>; 2]) -> Self {
+        Self::new(mins.into(), maxs.into())
+    }
+}
+
+/// Converts a box to its minimum and maximum points.
+impl From<BoundingBox> for [Point3<f32>; 2] {
+    fn from(bbox: BoundingBox) -> Self {
+        [bbox.mins.into(), bbox.maxs.into()]
+    }
+}
+
+/// Converts the centre and radius of a sphere.
+impl From<(Point3<f32>, f32)> for BoundingSphere {
+    fn from((center, radius): (Point3<f32>, f32)) -> Self {
+        Self{center: center.into(), radius}
+    }
+}
+
+/// Converts a sphere to its centre and radius.
+impl From<BoundingSphere> for (Point3<f32>, f32) {
+    fn from(sphere: BoundingSphere) -> Self {
+        (sphere.center.into(), sphere.radius)
+    }
+}
@@ -0,0 +1,38 @@
+//! Interop with `nalgebra` transforms and points.
+
+use {
+    crate::{BoundingBox, Mesh},
+    nalgebra::{Matrix4, Point3, Vector3},
+};
+
+impl Mesh {
+    /// Transforms this mesh as by [Mesh::transform], by any `nalgebra` transform that converts to
+    /// a [Matrix4], such as an `Isometry3`, `Similarity3` or `Affine3`.
+    ///
+    /// Arguments:
+    /// * `transform`: The transform.
+    pub fn transform_by(&mut self, transform: impl Into<Matrix4<f32>>) {
+        self.transform(transform.into().into());
+    }
+
+    /// Get the positions of this mesh's vertices as `nalgebra` points.
+    pub fn nalgebra_vertices(&self) -> impl ExactSizeIterator<Item = Point3<f32>> + '_ {
+        self.vertices.iter().map(|&v| v.into())
+    }
+
+    /// Get the normals of this mesh's vertices as `nalgebra` vectors, if they were generated.
+    pub fn nalgebra_normals(&self) -> impl ExactSizeIterator<Item = Vector3<f32>> + '_ {
+        self.normals.iter().map(|&n| n.into())
+    }
+}
+
+impl BoundingBox {
+    /// Get the smallest [BoundingBox] containing this box after it is transformed, as by
+    /// [BoundingBox::transformed], by any `nalgebra` transform that converts to a [Matrix4].
+    ///
+    /// Arguments:
+    /// * `transform`: The transform.
+    pub fn transformed_by(&self, transform: impl Into<Matrix4<f32>>) -> Self {
+        self.transformed(transform.into().into())
+    }
+}
@@ -0,0 +1,144 @@
+//! Exporting meshes as Wavefront OBJ.
+
+use {
+    crate::{Mesh, SubmeshKind},
+    std::io::{self, Write},
+};
+
+impl Mesh {
+    /// Writes this mesh in the Wavefront OBJ format, with its positions, normals, texture
+    /// coordinates and triangles, for interchange with modelling tools.
+    ///
+    /// With `groups`, the triangles of each [submesh](Mesh::submeshes) are put in a group named
+    /// `front`, `back`, `sides` or `plate`, so that each part can be given its own material;
+    /// any triangles outside the submeshes are put in a group named `other`.
+    ///
+    /// Arguments:
+    /// * `w`: The writer the mesh is written to. Many small writes are made, so it should be
+    ///   buffered.
+    /// * `groups`: Whether to group triangles by submesh.
+    pub fn write_obj<W: Write>(&self, mut w: W, groups: bool) -> io::Result<()> {
+        writeln!(w, "# trianglyph mesh")?;
+        for [x, y, z] in &self.vertices { writeln!(w, "v {x} {y} {z}")?; }
+        for [u, v] in &self.uvs { writeln!(w, "vt {u} {v}")?; }
+        for [x, y, z] in &self.normals { writeln!(w, "vn {x} {y} {z}")?; }
+
+        let has_uvs = !self.uvs.is_empty();
+        let has_normals = !self.normals.is_empty();
+        let faces = |w: &mut W, indices: &[u32]| -> io::Result<()> {
+            for tri in indices.as_chunks::<3>().0 {
+                write!(w, "f")?;
+                for i in tri.map(|i| i + 1) {
+                    match (has_uvs, has_normals) {
+                        (false, false) => write!(w, " {i}")?,
+                        (true, false) => write!(w, " {i}/{i}")?,
+                        (false, true) => write!(w, " {i}//{i}")?,
+                        (true, true) => write!(w, " {i}/{i}/{i}")?,
+                    }
+                }
+                writeln!(w)?;
+            }
+            Ok(())
+        };
+
+        if !groups || self.submeshes.is_empty() {
+            return faces(&mut w, &self.indices);
+        }
+
+        let mut covered = vec![false; self.indices.len()];
+        for (kind, range) in &self.submeshes {
+            let name = match kind {
+                SubmeshKind::Front => "front",
+                SubmeshKind::Back => "back",
+                SubmeshKind::Sides => "sides",
+                SubmeshKind::Plate => "plate",
+            };
+            writeln!(w, "g {name}")?;
+            let range = range.start as usize .. range.end as usize;
+            faces(&mut w, &self.indices[range.clone()])?;
+            covered[range].fill(true);
+        }
+
+        let other: Vec<u32> = self.indices.iter().zip(covered)
+            .filter_map(|(&i, covered)| (!covered).then_some(i))
+            .collect();
+        if !other.is_empty() {
+            writeln!(w, "g other")?;
+            faces(&mut w, &other)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_face, Config, Mesh, MeshGenerator, SubmeshKind, UvMapping};
+
+    /// The lines of `mesh` written as OBJ, with the group names and the number of faces in each
+    /// group, the faces before any group being counted in one with no name.
+    fn write(mesh: &Mesh, groups: bool) -> (Vec<String>, Vec<(String, usize)>) {
+        let mut obj = Vec::new();
+        mesh.write_obj(&mut obj, groups).unwrap();
+        let lines: Vec<String> = String::from_utf8(obj).unwrap().lines().map(Into::into).collect();
+        let mut groups = Vec::new();
+        for line in &lines {
+            match line.split_once(' ') {
+                Some(("g", name)) => groups.push((name.to_string(), 0)),
+                Some(("f", _)) if groups.is_empty() => groups.push((String::new(), 1)),
+                Some(("f", _)) => groups.last_mut().unwrap().1 += 1,
+                _ => {}
+            }
+        }
+        (lines, groups)
+    }
+
+    #[test]
+    fn obj_structure() {
+        let face = test_face();
+        let config = Config {
+            generate_normals: true,
+            uv_mapping: Some(UvMapping::GlyphBounds),
+            ..Default::default()
+        };
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let mesh = generator.generate_text("Bo").unwrap();
+        let count = |lines: &[String], tag: &str| {
+            lines.iter().filter(|line| line.split(' ').next() == Some(tag)).count()
+        };
+
+        let (lines, groups) = write(&mesh, true);
+        for tag in ["v", "vt", "vn"] { assert_eq!(count(&lines, tag), mesh.vertices.len()); }
+        assert_eq!(count(&lines, "f"), mesh.indices.len() / 3);
+        let expected: Vec<(String, usize)> = mesh.submeshes.iter()
+            .map(|(kind, range)| {
+                let name = match kind {
+                    SubmeshKind::Front => "front",
+                    SubmeshKind::Back => "back",
+                    SubmeshKind::Sides => "sides",
+                    SubmeshKind::Plate => "plate",
+                };
+                (name.to_string(), range.len() / 3)
+            })
+            .collect();
+        assert_eq!(groups, expected);
+        assert!(groups.iter().any(|(name, _)| name == "sides"));
+
+        // triangles outside the submeshes are grouped together at the end
+        let mut partial = mesh.clone();
+        partial.submeshes.retain(|(kind, _)| *kind != SubmeshKind::Sides);
+        let (_, groups) = write(&partial, true);
+        let sides: usize = mesh.submeshes.iter()
+            .filter(|(kind, _)| *kind == SubmeshKind::Sides)
+            .map(|(_, range)| range.len() / 3)
+            .sum();
+        assert_eq!(groups.last().unwrap(), &("other".into(), sides));
+
+        let (_, groups) = write(&mesh, false);
+        assert_eq!(groups, [(String::new(), mesh.indices.len() / 3)]);
+
+        let space = generator.generate_text(" ").unwrap();
+        let (lines, groups) = write(&space, true);
+        assert_eq!(count(&lines, "v") + count(&lines, "f"), 0);
+        assert!(groups.iter().all(|&(_, faces)| faces == 0));
+    }
+}
@@ -0,0 +1,226 @@
+//! A [MeshGenerator] that owns its font data.
+
+use crate::{
+    BorderedMesh, ColorMesh, Config, Error, GlyphId, GlyphMesh, InstancedText, Mesh, MeshGenerator,
+    MeshSink, PlacedGlyph, Result, SplitMesh, Tag, TextLayout, TextMesh, TextMetrics, VertexBuffers,
+    VertexCtor,
+};
+
+self_cell::self_cell!(
+    struct OwnedFace {
+        owner: Box<[u8]>,
+
+        #[covariant]
+        dependent: MeshGenerator,
+    }
+);
+
+/// Generates glyph meshes for a font whose data it owns.
+///
+/// This is a [MeshGenerator] without a lifetime parameter, for storing alongside other owned
+/// state, such as in an ECS resource. The mesh generation methods are forwarded; the rest of the
+/// [MeshGenerator] API is available through [generator](Self::generator).
+pub struct OwnedMeshGenerator(OwnedFace);
+
+impl OwnedMeshGenerator {
+    /// Creates a new [OwnedMeshGenerator] by parsing a face from font data.
+    ///
+    /// Arguments:
+    /// * `data`: The font data.
+    /// * `index`: The index of the face within a font collection, or 0.
+    ///
+    /// Returns:
+    /// A [Result] containing the [OwnedMeshGenerator] if successful, otherwise an
+    /// [Error](crate::Error).
+    pub fn new(data: impl Into<Box<[u8]>>, index: u32) -> Result<Self> {
+        Self::new_with_config(data, index, Config::default())
+    }
+
+    /// Creates a new [OwnedMeshGenerator] with custom quality settings by parsing a face from
+    /// font data.
+    ///
+    /// Arguments:
+    /// * `data`: The font data.
+    /// * `index`: The index of the face within a font collection, or 0.
+    /// * `config`: The [Config] that should be used.
+    ///
+    /// Returns:
+    /// A [Result] containing the [OwnedMeshGenerator] if successful, otherwise an
+    /// [Error](crate::Error).
+    pub fn new_with_config(data: impl Into<Box<[u8]>>, index: u32, config: Config)
+        -> Result<Self>
+    {
+        OwnedFace::try_new(data.into(), |data| {
+            MeshGenerator::from_bytes_with_config(data, index, config)
+        })
+        .map(Self)
+    }
+
+    /// Get the [MeshGenerator] borrowing the owned font data.
+    pub fn generator(&self) -> &MeshGenerator<'_> {
+        self.0.borrow_dependent()
+    }
+
+    /// Get the font data.
+    pub fn data(&self) -> &[u8] {
+        self.0.borrow_owner()
+    }
+
+    /// Sets the location in the face's design space at which glyphs are outlined. See
+    /// [MeshGenerator::set_variation].
+    pub fn set_variation(&mut self, axes: &[(Tag, f32)]) -> Result<()> {
+        self.0.with_dependent_mut(|_, generator| generator.set_variation(axes))
+    }
+
+    /// Moves to one of the face's named instances. See [MeshGenerator::set_named_instance].
+    pub fn set_named_instance(&mut self, index: usize) -> Result<()> {
+        self.0.with_dependent_mut(|_, generator| generator.set_named_instance(index))
+    }
+
+    /// Moves to the face's named instance with the given name. See
+    /// [MeshGenerator::set_named_instance_by_name].
+    pub fn set_named_instance_by_name(&mut self, name: &str) -> Result<()> {
+        self.0.with_dependent_mut(|_, generator| generator.set_named_instance_by_name(name))
+    }
+
+    /// Empties the glyph mesh cache. See [MeshGenerator::clear_cache].
+    pub fn clear_cache(&mut self) {
+        self.0.with_dependent_mut(|_, generator| generator.clear_cache());
+    }
+
+    /// Meshes the glyphs of each character in `chars` ahead of time. See
+    /// [MeshGenerator::precache].
+    pub fn precache(&mut self, chars: &str) -> Vec<(char, Error)> {
+        self.0.with_dependent_mut(|_, generator| generator.precache(chars))
+    }
+
+    /// Get the number of glyph meshes in the cache. See [MeshGenerator::cache_len].
+    pub fn cache_len(&self) -> usize {
+        self.generator().cache_len()
+    }
+
+    /// Generates the [Mesh] for the given glyph. See [MeshGenerator::generate_mesh].
+    pub fn generate_mesh(&self, glyph: GlyphId) -> Result<Mesh> {
+        self.generator().generate_mesh(glyph)
+    }
+
+    /// Generates a [Mesh] for each of the given glyphs. See [MeshGenerator::generate_meshes].
+    pub fn generate_meshes(&self, glyphs: &[GlyphId]) -> Result<Vec<Mesh>> {
+        self.generator().generate_meshes(glyphs)
+    }
+
+    /// Generates the [Mesh] for the given glyph into an existing mesh. See
+    /// [MeshGenerator::generate_mesh_into].
+    pub fn generate_mesh_into(&self, glyph: GlyphId, mesh: &mut Mesh) -> Result<()> {
+        self.generator().generate_mesh_into(glyph, mesh)
+    }
+
+    /// Generates the [Mesh] for the given glyph with its horizontal metrics. See
+    /// [MeshGenerator::generate_glyph_mesh].
+    pub fn generate_glyph_mesh(&self, glyph: GlyphId) -> Result<GlyphMesh> {
+        self.generator().generate_glyph_mesh(glyph)
+    }
+
+    /// Generates the [SplitMesh] for the given glyph. See [MeshGenerator::generate_mesh_split].
+    pub fn generate_mesh_split(&self, glyph: GlyphId) -> Result<SplitMesh> {
+        self.generator().generate_mesh_split(glyph)
+    }
+
+    /// Passes the mesh of the given glyph to a [MeshSink]. See
+    /// [MeshGenerator::generate_mesh_visit].
+    pub fn generate_mesh_visit(&self, glyph: GlyphId, sink: &mut impl MeshSink) -> Result<()> {
+        self.generator().generate_mesh_visit(glyph, sink)
+    }
+
+    /// Generates the vertices of the given glyph in a caller's own vertex format. See
+    /// [MeshGenerator::generate_vertices].
+    pub fn generate_vertices<V>(&self, glyph: GlyphId, ctor: &mut impl VertexCtor<V>)
+        -> Result<VertexBuffers<V>>
+    {
+        self.generator().generate_vertices(glyph, ctor)
+    }
+
+    /// Generates a chain of [Mesh]es for the given glyph at several tolerances. See
+    /// [MeshGenerator::generate_mesh_lods].
+    pub fn generate_mesh_lods(&self, glyph: GlyphId, tolerances: &[f32]) -> Result<Vec<Mesh>> {
+        self.generator().generate_mesh_lods(glyph, tolerances)
+    }
+
+    /// Generates a flat, double-sided [Mesh] for the given glyph. See
+    /// [MeshGenerator::generate_flat_double_sided].
+    pub fn generate_flat_double_sided(&self, glyph: GlyphId) -> Result<Mesh> {
+        self.generator().generate_flat_double_sided(glyph)
+    }
+
+    /// Generates the fill and border of the given glyph as ranges of one mesh. See
+    /// [MeshGenerator::generate_bordered].
+    pub fn generate_bordered(&self, glyph: GlyphId, width: f32) -> Result<BorderedMesh> {
+        self.generator().generate_bordered(glyph, width)
+    }
+
+    /// Generates a [Mesh] of the layers of the given color glyph, with the range and color of
+    /// each. See [MeshGenerator::generate_color_layers].
+    pub fn generate_color_layers(&self, glyph: GlyphId, palette: u16) -> Result<ColorMesh> {
+        self.generator().generate_color_layers(glyph, palette)
+    }
+
+    /// Generates a single [Mesh] for some text. See [MeshGenerator::generate_text].
+    pub fn generate_text(&self, text: &str) -> Result<Mesh> {
+        self.generator().generate_text(text)
+    }
+
+    /// Generates a single [Mesh] for some text, with the bounds of each of its lines. See
+    /// [MeshGenerator::layout_text].
+    pub fn layout_text(&self, text: &str, layout: &TextLayout) -> Result<TextMesh> {
+        self.generator().layout_text(text, layout)
+    }
+
+    /// Generates a single [Mesh] for some text, meshing its glyphs in parallel. See
+    /// [MeshGenerator::layout_text_parallel].
+    #[cfg(feature = "rayon")]
+    pub fn layout_text_parallel(&self, text: &str, layout: &TextLayout) -> Result<TextMesh> {
+        self.generator().layout_text_parallel(text, layout)
+    }
+
+    /// Measures some text without meshing it. See [MeshGenerator::measure].
+    pub fn measure(&self, text: &str, layout: &TextLayout) -> Result<TextMetrics> {
+        self.generator().measure(text, layout)
+    }
+
+    /// Computes the fraction of some text's advance boxes that its glyphs cover. See
+    /// [MeshGenerator::ink_coverage].
+    pub fn ink_coverage(&self, text: &str, layout: &TextLayout) -> Result<f32> {
+        self.generator().ink_coverage(text, layout)
+    }
+
+    /// Generates the distinct glyph meshes of some text, with a transform for each place they
+    /// appear. See [MeshGenerator::layout_text_instanced].
+    pub fn layout_text_instanced(&self, text: &str, layout: &TextLayout) -> Result<InstancedText> {
+        self.generator().layout_text_instanced(text, layout)
+    }
+
+    /// Generates the mesh of each glyph of some text separately, with its position. See
+    /// [MeshGenerator::layout_text_glyphs].
+    pub fn layout_text_glyphs(&self, text: &str, layout: &TextLayout) -> Result<Vec<PlacedGlyph>> {
+        self.generator().layout_text_glyphs(text, layout)
+    }
+
+    /// Writes the outline of the given glyph as DXF polylines. See
+    /// [MeshGenerator::write_glyph_dxf].
+    pub fn write_glyph_dxf<W: std::io::Write>(&self, glyph: GlyphId, w: W) -> Result<()> {
+        self.generator().write_glyph_dxf(glyph, w)
+    }
+
+    /// Writes the outlines of some text as DXF polylines. See [MeshGenerator::write_text_dxf].
+    pub fn write_text_dxf<W: std::io::Write>(&self, text: &str, layout: &TextLayout, w: W)
+        -> Result<()>
+    {
+        self.generator().write_text_dxf(text, layout, w)
+    }
+
+    /// Writes the flattened outline of the given glyph as an SVG path. See
+    /// [MeshGenerator::write_glyph_svg].
+    pub fn write_glyph_svg<W: std::io::Write>(&self, glyph: GlyphId, w: W) -> Result<()> {
+        self.generator().write_glyph_svg(glyph, w)
+    }
+}
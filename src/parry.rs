@@ -0,0 +1,52 @@
+//! Collision shapes for the `parry3d` physics library, as used by Rapier.
+
+use {
+    crate::Mesh,
+    parry3d::{math::Point, shape::{SharedShape, TriMesh}},
+};
+
+/// Vertices and triangles, as `parry3d` takes them.
+type Geometry = (Vec<Point<f32>>, Vec<[u32; 3]>);
+
+impl Mesh {
+    /// Converts this mesh to a `parry3d` triangle mesh, for use as a static or kinematic
+    /// collider.
+    ///
+    /// The mesh is [welded](Mesh::weld) first, so that the triangles share their edges and the
+    /// collider has no cracks for contacts to catch on.
+    ///
+    /// Returns:
+    /// The [TriMesh], or `None` if the mesh has no triangles.
+    pub fn to_trimesh(&self) -> Option<TriMesh> {
+        let (vertices, indices) = self.collision_geometry()?;
+        TriMesh::new(vertices, indices).ok()
+    }
+
+    /// Decomposes this mesh into convex parts with `parry3d`'s V-HACD, as a compound shape that
+    /// can be used as a dynamic collider.
+    ///
+    /// Decomposition is much slower than meshing, so the result is best built once and kept.
+    /// For a single, cheaper convex shape, see [Mesh::convex_hull].
+    ///
+    /// Returns:
+    /// The compound [SharedShape], or `None` if the mesh has no triangles.
+    pub fn to_convex_decomposition(&self) -> Option<SharedShape> {
+        let (vertices, indices) = self.collision_geometry()?;
+        Some(SharedShape::convex_decomposition(&vertices, &indices))
+    }
+
+    /// The welded vertices and triangles of this mesh, as `parry3d` takes them, or `None` if it
+    /// has no triangles.
+    fn collision_geometry(&self) -> Option<Geometry> {
+        let mut welded = Mesh {
+            vertices: self.vertices.clone(),
+            indices: self.indices.clone(),
+            ..Default::default()
+        };
+        welded.weld(0.);
+        if welded.indices.is_empty() { return None; }
+
+        let vertices = welded.vertices.into_iter().map(Point::from).collect();
+        Some((vertices, welded.indices.as_chunks::<3>().0.to_vec()))
+    }
+}
@@ -0,0 +1,117 @@
+//! Exporting meshes as PLY.
+
+use {
+    crate::Mesh,
+    std::io::{self, Write},
+};
+
+impl Mesh {
+    /// Writes this mesh in the binary little-endian PLY format, with its positions, normals and
+    /// texture coordinates, and optionally a color for each vertex, for point cloud and scan
+    /// processing tools.
+    ///
+    /// Normals are written as the `nx`, `ny` and `nz` properties and texture coordinates as `s`
+    /// and `t`, if the mesh has them. Colors are written as the `red`, `green`, `blue` and
+    /// `alpha` properties.
+    ///
+    /// Arguments:
+    /// * `w`: The writer the mesh is written to.
+    /// * `colors`: The sRGB red, green, blue and alpha of each vertex, if any.
+    ///
+    /// Returns:
+    /// An [io::Error] of kind [io::ErrorKind::InvalidInput] if there isn't a color for each
+    /// vertex, or if writing fails.
+    pub fn write_ply<W: Write>(&self, mut w: W, colors: Option<&[[u8; 4]]>) -> io::Result<()> {
+        if colors.is_some_and(|c| c.len() != self.vertices.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "color count does not match vertex count",
+            ));
+        }
+
+        writeln!(w, "ply")?;
+        writeln!(w, "format binary_little_endian 1.0")?;
+        writeln!(w, "comment trianglyph mesh")?;
+        writeln!(w, "element vertex {}", self.vertices.len())?;
+        for p in ["x", "y", "z"] { writeln!(w, "property float {p}")?; }
+        if !self.normals.is_empty() {
+            for p in ["nx", "ny", "nz"] { writeln!(w, "property float {p}")?; }
+        }
+        if !self.uvs.is_empty() {
+            for p in ["s", "t"] { writeln!(w, "property float {p}")?; }
+        }
+        if colors.is_some() {
+            for p in ["red", "green", "blue", "alpha"] { writeln!(w, "property uchar {p}")?; }
+        }
+        writeln!(w, "element face {}", self.indices.len() / 3)?;
+        writeln!(w, "property list uchar uint vertex_indices")?;
+        writeln!(w, "end_header")?;
+
+        for (i, v) in self.vertices.iter().enumerate() {
+            let floats = v.iter()
+                .chain(self.normals.get(i).into_iter().flatten())
+                .chain(self.uvs.get(i).into_iter().flatten());
+            for x in floats { w.write_all(&x.to_le_bytes())?; }
+            if let Some(colors) = colors { w.write_all(&colors[i])?; }
+        }
+        for tri in self.indices.as_chunks::<3>().0 {
+            w.write_all(&[3])?;
+            for i in tri { w.write_all(&i.to_le_bytes())?; }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{test_face, Config, Mesh, MeshGenerator, UvMapping},
+        std::io,
+    };
+
+    /// The header lines of `mesh` written as PLY, and the length of the body after them.
+    fn write(mesh: &Mesh, colors: Option<&[[u8; 4]]>) -> (Vec<String>, usize) {
+        let mut ply = Vec::new();
+        mesh.write_ply(&mut ply, colors).unwrap();
+        let end = ply.windows(11).position(|w| w == b"end_header\n").unwrap() + 11;
+        let header = std::str::from_utf8(&ply[.. end]).unwrap();
+        (header.lines().map(Into::into).collect(), ply.len() - end)
+    }
+
+    #[test]
+    fn ply_structure() {
+        let face = test_face();
+        let config = Config {
+            generate_normals: true,
+            uv_mapping: Some(UvMapping::GlyphBounds),
+            ..Default::default()
+        };
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let mesh = generator.generate_text("Bo").unwrap();
+        let (n, faces) = (mesh.vertices.len(), mesh.indices.len() / 3);
+        let has = |header: &[String], line: &str| header.iter().any(|l| l == line);
+
+        let colors = vec![[255, 0, 0, 255]; n];
+        let (header, body) = write(&mesh, Some(&colors));
+        assert!(has(&header, &format!("element vertex {n}")));
+        assert!(has(&header, &format!("element face {faces}")));
+        assert!(has(&header, "property float nx") && has(&header, "property uchar alpha"));
+        // positions, normals and texture coordinates, colors, then triangles
+        assert_eq!(body, n * (4 * (3 + 3 + 2) + 4) + faces * (1 + 3 * 4));
+
+        let mut bare = mesh.clone();
+        bare.normals.clear();
+        bare.uvs.clear();
+        let (header, body) = write(&bare, None);
+        assert!(!has(&header, "property float nx") && !has(&header, "property uchar alpha"));
+        assert_eq!(body, n * 4 * 3 + faces * (1 + 3 * 4));
+
+        let error = mesh.write_ply(io::sink(), Some(&colors[1 ..])).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        let space = generator.generate_text(" ").unwrap();
+        let (header, body) = write(&space, None);
+        assert!(has(&header, "element vertex 0") && has(&header, "element face 0"));
+        assert_eq!(body, 0);
+    }
+}
@@ -0,0 +1,281 @@
+//! Mesh simplification by edge collapse, after Garland and Heckbert's "Surface Simplification
+//! Using Quadric Error Metrics".
+
+use {
+    crate::{cross, dot, length, normalize, sub, Mesh},
+    std::{cmp::Reverse, collections::BinaryHeap},
+};
+
+/// How much more an edge of an open surface resists moving than the surface itself does, to keep
+/// the outline of flat meshes.
+const BOUNDARY_WEIGHT: f64 = 100.;
+
+/// The upper triangle of a symmetric 4x4 matrix, summing the squared distances to a set of
+/// planes.
+#[derive(Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// The quadric of the plane through `p` with unit `normal`, scaled by `weight`.
+    fn plane(normal: [f32; 3], p: [f32; 3], weight: f64) -> Self {
+        let [a, b, c] = normal.map(f64::from);
+        let d = -(a * p[0] as f64 + b * p[1] as f64 + c * p[2] as f64);
+        Self([a*a, a*b, a*c, a*d, b*b, b*c, b*d, c*c, c*d, d*d].map(|x| x * weight))
+    }
+
+    fn add(&mut self, other: &Self) {
+        for (x, y) in self.0.iter_mut().zip(other.0) { *x += y; }
+    }
+
+    /// The weighted sum of squared distances from `p` to the planes.
+    fn error(&self, p: [f32; 3]) -> f64 {
+        let [x, y, z] = p.map(f64::from);
+        let [aa, ab, ac, ad, bb, bc, bd, cc, cd, dd] = self.0;
+        x*x*aa + y*y*bb + z*z*cc + dd
+            + 2. * (x*y*ab + x*z*ac + y*z*bc + x*ad + y*bd + z*cd)
+    }
+}
+
+impl Mesh {
+    /// Reduces the number of triangles in this mesh by collapsing its edges, cheapest first, so
+    /// that distant text can be drawn with a fraction of the triangles while keeping its
+    /// silhouette.
+    ///
+    /// The cost of collapsing an edge is how far it moves the surface away from the planes of
+    /// the triangles around it, so flat areas such as caps are simplified first, and the sharp
+    /// edges where caps meet side walls, and the outline of flat meshes, are kept for as long as
+    /// possible. Collapses that would fold triangles over or make the surface non-manifold are
+    /// skipped, so the result may have more triangles than asked for.
+    ///
+    /// The mesh is [welded](Mesh::weld) first, so its normals and texture coordinates are
+    /// cleared. The [submeshes](Mesh::submeshes) are kept in step with the indices.
+    ///
+    /// Arguments:
+    /// * `target_ratio`: The fraction of the mesh's triangles to keep, from 0 to 1.
+    pub fn simplify(&mut self, target_ratio: f32) {
+        self.weld(0.);
+        self.collapse_edges(target_ratio);
+    }
+
+    /// Simplifies this welded mesh, as [simplify](Mesh::simplify) does.
+    ///
+    /// Returns: Which of the mesh's triangles survive, in order.
+    fn collapse_edges(&mut self, target_ratio: f32) -> Vec<bool> {
+        let mut tris: Vec<[u32; 3]> = self.indices.as_chunks::<3>().0.to_vec();
+        let mut alive = vec![true; tris.len()];
+        let mut live = tris.len();
+        let target = (target_ratio.clamp(0., 1.) * tris.len() as f32) as usize;
+
+        let mut around = vec![Vec::new(); self.vertices.len()];
+        for (t, tri) in tris.iter().enumerate() {
+            for &i in tri { around[i as usize].push(t); }
+        }
+
+        // each vertex starts with the planes of its triangles, weighted by their area
+        let mut quadrics = vec![Quadric::default(); self.vertices.len()];
+        let mut edge_count = std::collections::HashMap::new();
+        for tri in &tris {
+            let [a, b, c] = tri.map(|i| self.vertices[i as usize]);
+            let n = cross(sub(b, a), sub(c, a));
+            let q = Quadric::plane(normalize(n), a, 0.5 * length(n) as f64);
+            for &i in tri { quadrics[i as usize].add(&q); }
+            for k in 0 .. 3 {
+                let (i, j) = (tri[k], tri[(k + 1) % 3]);
+                *edge_count.entry((i.min(j), i.max(j))).or_insert(0) += 1;
+            }
+        }
+
+        // edges with a triangle on one side only are held in place by a perpendicular plane
+        for tri in &tris {
+            let [a, b, c] = tri.map(|i| self.vertices[i as usize]);
+            let n = normalize(cross(sub(b, a), sub(c, a)));
+            for k in 0 .. 3 {
+                let (i, j) = (tri[k], tri[(k + 1) % 3]);
+                if edge_count[&(i.min(j), i.max(j))] != 1 { continue; }
+                let (p, q) = (self.vertices[i as usize], self.vertices[j as usize]);
+                let edge = sub(q, p);
+                let weight = BOUNDARY_WEIGHT * dot(edge, edge) as f64;
+                let plane = Quadric::plane(normalize(cross(edge, n)), p, weight);
+                quadrics[i as usize].add(&plane);
+                quadrics[j as usize].add(&plane);
+            }
+        }
+
+        // the facing of each triangle as generated, which collapses may turn but never reverse,
+        // however many of them add up
+        let normal = |[a, b, c]: [[f32; 3]; 3]| cross(sub(b, a), sub(c, a));
+        let facing: Vec<[f32; 3]> = tris.iter()
+            .map(|tri| normal(tri.map(|i| self.vertices[i as usize])))
+            .collect();
+
+        let mut versions = vec![0u32; self.vertices.len()];
+        let mut heap = BinaryHeap::new();
+        let push = |heap: &mut BinaryHeap<_>, vertices: &[[f32; 3]], quadrics: &[Quadric],
+                    versions: &[u32], i: u32, j: u32| {
+            let (p, q) = (vertices[i as usize], vertices[j as usize]);
+            let mut sum = quadrics[i as usize];
+            sum.add(&quadrics[j as usize]);
+            let mid = std::array::from_fn(|k| 0.5 * (p[k] + q[k]));
+            let (cost, to) = [p, q, mid].into_iter()
+                .map(|to| (sum.error(to).max(0.), to))
+                .min_by(|x, y| x.0.total_cmp(&y.0))
+                .unwrap();
+            let key = (cost.to_bits(), i, j, versions[i as usize], versions[j as usize]);
+            heap.push(Reverse((key, to.map(f32::to_bits))));
+        };
+        for &(i, j) in edge_count.keys() {
+            push(&mut heap, &self.vertices, &quadrics, &versions, i, j);
+        }
+
+        while live > target {
+            let Some(Reverse(((_, i, j, vi, vj), to))) = heap.pop() else { break };
+            if versions[i as usize] != vi || versions[j as usize] != vj { continue; }
+            let to = to.map(f32::from_bits);
+
+            // the triangles on the edge, and the others around either end
+            let (shared, moved): (Vec<usize>, Vec<usize>) = around[i as usize].iter()
+                .chain(&around[j as usize])
+                .copied()
+                .filter(|&t| alive[t])
+                .partition(|&t| tris[t].contains(&i) && tris[t].contains(&j));
+            let mut shared = shared;
+            shared.sort_unstable();
+            shared.dedup();
+
+            // the ends may only share the neighbours across the edge's own triangles
+            let ni = neighbours(&around[i as usize], &alive, &tris, [i, j]);
+            let nj = neighbours(&around[j as usize], &alive, &tris, [i, j]);
+            let common = ni.iter().filter(|u| nj.binary_search(u).is_ok()).count();
+            if shared.is_empty() || common != shared.len() { continue; }
+
+            // and no triangle may turn over
+            let folds = moved.iter().any(|&t| {
+                let at = |v: u32| self.vertices[v as usize];
+                let before = normal(tris[t].map(at));
+                let after = normal(tris[t].map(|v| if v == i || v == j {to} else {at(v)}));
+                dot(before, after) <= 0. || dot(facing[t], after) <= 0.
+            });
+            if folds { continue; }
+
+            for &t in &shared {
+                alive[t] = false;
+                live -= 1;
+            }
+            for &t in &moved {
+                for v in &mut tris[t] { if *v == j { *v = i; } }
+            }
+            let from_j = std::mem::take(&mut around[j as usize]);
+            around[i as usize].extend(from_j);
+            around[i as usize].retain(|&t| alive[t]);
+            around[i as usize].sort_unstable();
+            around[i as usize].dedup();
+
+            self.vertices[i as usize] = to;
+            let q = quadrics[j as usize];
+            quadrics[i as usize].add(&q);
+            versions[i as usize] += 1;
+            versions[j as usize] += 1;
+            for n in neighbours(&around[i as usize], &alive, &tris, [i, j]) {
+                push(&mut heap, &self.vertices, &quadrics, &versions, i, n);
+            }
+        }
+
+        // keep the surviving triangles in order, counting the indices kept before each so that
+        // the submesh ranges can follow
+        let mut indices = Vec::with_capacity(live * 3);
+        let mut kept_before = Vec::with_capacity(tris.len() + 1);
+        for (tri, &alive) in tris.iter().zip(&alive) {
+            kept_before.push(indices.len() as u32);
+            if alive { indices.extend(tri); }
+        }
+        kept_before.push(indices.len() as u32);
+        for (_, range) in &mut self.submeshes {
+            *range = kept_before[range.start as usize / 3] .. kept_before[range.end as usize / 3];
+        }
+        self.indices = indices;
+        self.optimize_vertex_fetch();
+        alive
+    }
+}
+
+/// The vertices sharing a live triangle with the vertex that `around` lists the triangles of,
+/// other than those in `skip`, in ascending order.
+fn neighbours(around: &[usize], alive: &[bool], tris: &[[u32; 3]], skip: [u32; 2]) -> Vec<u32> {
+    let mut found: Vec<u32> = around.iter()
+        .filter(|&&t| alive[t])
+        .flat_map(|&t| tris[t])
+        .filter(|u| !skip.contains(u))
+        .collect();
+    found.sort_unstable();
+    found.dedup();
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cross, dot, sub, test_face, Config, Mesh, MeshGenerator};
+
+    /// The normal of each triangle in `mesh`, scaled by twice its area.
+    fn normals(mesh: &Mesh) -> Vec<[f32; 3]> {
+        mesh.indices.as_chunks::<3>().0.iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| mesh.vertices[i as usize]);
+                cross(sub(b, a), sub(c, a))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn simplify_keeps_orientation() {
+        let face = test_face();
+        let config = Config{tolerance: 0.002, generate_normals: true, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        // the curved glyphs are simplified deep enough that the turns of successive collapses
+        // add up; B's straight walls can't lose much more than half their triangles
+        let cases = [('O', 0.5), ('O', 0.1), ('B', 0.5), ('g', 0.5), ('g', 0.1), ('&', 0.1)];
+        for (c, ratio) in cases {
+            let mut mesh = generator.generate_mesh(face.glyph_index(c).unwrap()).unwrap();
+            mesh.weld(0.);
+            let before = normals(&mesh);
+            let alive = mesh.collapse_edges(ratio);
+            let kept = (mesh.indices.len() / 3) as f32 / before.len() as f32;
+            assert!((kept - ratio).abs() < 0.05, "{c}: kept {kept} of {ratio}");
+
+            assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.vertices.len()));
+            let mut end = 0;
+            for (_, range) in &mesh.submeshes {
+                assert_eq!(range.start, end);
+                assert!(range.end >= range.start && range.end % 3 == 0);
+                end = range.end;
+            }
+            assert_eq!(end as usize, mesh.indices.len());
+
+            // the survivors keep their order, and each faces the way it did, but for slivers of
+            // no area, which the tessellator leaves some of
+            let survivors = before.iter().zip(alive).filter(|&(_, alive)| alive);
+            for (t, ((before, _), after)) in survivors.zip(normals(&mesh)).enumerate() {
+                assert!(dot(*before, after) >= 0., "{c}: triangle {t} turned over");
+            }
+        }
+    }
+
+    #[test]
+    fn simplify_flat_text() {
+        let face = test_face();
+        let config = Config{tolerance: 0.002, extrude: None, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let original = generator.generate_text("aBg").unwrap();
+        let mut mesh = original.clone();
+        mesh.simplify(0.3);
+
+        assert!(mesh.indices.len() < original.indices.len());
+        assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.vertices.len()));
+        // the tessellator leaves some slivers of no area, which may stay that way
+        for t in mesh.indices.as_chunks::<3>().0 {
+            let [a, b, c] = t.map(|i| mesh.vertices[i as usize]);
+            assert!(cross(sub(b, a), sub(c, a))[2] >= 0., "{t:?} turned over");
+        }
+        // the outline is held in place, so the text keeps its extent
+        assert_eq!(mesh.bbox, original.bbox);
+    }
+}
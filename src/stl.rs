@@ -0,0 +1,105 @@
+//! Exporting meshes as STL, for 3D printing.
+
+use {
+    crate::{cross, normalize, sub, Mesh},
+    std::{borrow::Cow, io::{self, Write}},
+};
+
+impl Mesh {
+    /// Writes this mesh in the binary STL format, for slicers and other 3D printing tools.
+    ///
+    /// STL has no units; slicers usually read it in millimetres, so the mesh may need
+    /// [scaling](Mesh::scale) first. Only positions are written, with a facet normal computed
+    /// from each triangle.
+    ///
+    /// Arguments:
+    /// * `w`: The writer the mesh is written to.
+    /// * `weld`: If set, the mesh is [welded](Mesh::weld) with this epsilon first, so that
+    ///   vertices that nearly meet, as where glyphs touch, are joined and slicers see a
+    ///   watertight solid.
+    pub fn write_stl<W: Write>(&self, mut w: W, weld: Option<f32>) -> io::Result<()> {
+        let mesh = self.welded_for_stl(weld);
+        let count = u32::try_from(mesh.indices.len() / 3)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "mesh too large"))?;
+
+        let mut header = [0; 80];
+        header[.. 16].copy_from_slice(b"trianglyph mesh ");
+        w.write_all(&header)?;
+        w.write_all(&count.to_le_bytes())?;
+        for tri in mesh.indices.as_chunks::<3>().0 {
+            let corners = tri.map(|i| mesh.vertices[i as usize]);
+            for v in [facet_normal(corners)].iter().chain(&corners) {
+                for x in v { w.write_all(&x.to_le_bytes())?; }
+            }
+            w.write_all(&0u16.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Writes this mesh in the ASCII STL format, as [Mesh::write_stl] does in binary.
+    ///
+    /// Arguments:
+    /// * `w`: The writer the mesh is written to. Many small writes are made, so it should be
+    ///   buffered.
+    /// * `weld`: If set, the mesh is [welded](Mesh::weld) with this epsilon first.
+    pub fn write_stl_ascii<W: Write>(&self, mut w: W, weld: Option<f32>) -> io::Result<()> {
+        let mesh = self.welded_for_stl(weld);
+        writeln!(w, "solid trianglyph")?;
+        for tri in mesh.indices.as_chunks::<3>().0 {
+            let corners = tri.map(|i| mesh.vertices[i as usize]);
+            let [x, y, z] = facet_normal(corners);
+            writeln!(w, "facet normal {x:e} {y:e} {z:e}")?;
+            writeln!(w, "  outer loop")?;
+            for [x, y, z] in corners { writeln!(w, "    vertex {x:e} {y:e} {z:e}")?; }
+            writeln!(w, "  endloop")?;
+            writeln!(w, "endfacet")?;
+        }
+        writeln!(w, "endsolid trianglyph")
+    }
+
+    /// This mesh, welded with `epsilon` if it is set.
+    fn welded_for_stl(&self, epsilon: Option<f32>) -> Cow<'_, Mesh> {
+        let Some(epsilon) = epsilon else { return Cow::Borrowed(self) };
+        let mut mesh = Mesh {
+            vertices: self.vertices.clone(),
+            indices: self.indices.clone(),
+            ..Default::default()
+        };
+        mesh.weld(epsilon);
+        Cow::Owned(mesh)
+    }
+}
+
+fn facet_normal([a, b, c]: [[f32; 3]; 3]) -> [f32; 3] {
+    normalize(cross(sub(b, a), sub(c, a)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_face, Config, MeshGenerator};
+
+    #[test]
+    fn stl_structure() {
+        let face = test_face();
+        let config = Config{generate_normals: true, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let mesh = generator.generate_text("Bo").unwrap();
+        let space = generator.generate_text(" ").unwrap();
+
+        // the header's count matches the triangles that follow, welded or not
+        for (mesh, weld) in [(&mesh, None), (&mesh, Some(1e-4)), (&space, None)] {
+            let mut stl = Vec::new();
+            mesh.write_stl(&mut stl, weld).unwrap();
+            let count = u32::from_le_bytes(stl[80 .. 84].try_into().unwrap()) as usize;
+            assert_eq!(count, mesh.indices.len() / 3);
+            assert_eq!(stl.len(), 84 + 50 * count);
+
+            let mut ascii = Vec::new();
+            mesh.write_stl_ascii(&mut ascii, weld).unwrap();
+            let ascii = String::from_utf8(ascii).unwrap();
+            assert!(ascii.starts_with("solid ") && ascii.ends_with("endsolid trianglyph\n"));
+            assert_eq!(ascii.matches("facet normal").count(), count);
+            assert_eq!(ascii.matches("vertex").count(), 3 * count);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Outlining glyphs from the documents in a face's `SVG` table.
+
+use {
+    ttf_parser::{GlyphId, OutlineBuilder},
+    usvg::tiny_skia_path::{PathSegment, Point},
+};
+
+/// Outlines `glyph` from its document in the `SVG` table of `face`, if it has one, into
+/// `builder`. Only the filled paths of the glyph's element are used; strokes, paint and the fill
+/// rule are ignored.
+///
+/// Returns:
+/// Whether the glyph had an outline in the `SVG` table.
+pub(crate) fn outline_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph: GlyphId,
+    builder: &mut dyn OutlineBuilder,
+) -> bool {
+    let Some(data) = face.glyph_svg_image(glyph) else { return false };
+    let Ok(tree) = usvg::Tree::from_data(data, &usvg::Options::default()) else { return false };
+
+    // a document may hold several glyphs, each in an element with an ID naming it
+    match tree.node_by_id(&format!("glyph{}", glyph.0)) {
+        Some(node) => outline_nodes(std::slice::from_ref(node), builder),
+        None => outline_nodes(tree.root().children(), builder),
+    }
+}
+
+fn outline_nodes(nodes: &[usvg::Node], builder: &mut dyn OutlineBuilder) -> bool {
+    let mut outlined = false;
+    for node in nodes {
+        match node {
+            usvg::Node::Group(group) => outlined |= outline_nodes(group.children(), builder),
+            usvg::Node::Path(path) if path.fill().is_some() => {
+                outline_path(path, builder);
+                outlined = true;
+            }
+            _ => { }
+        }
+    }
+    outlined
+}
+
+fn outline_path(path: &usvg::Path, builder: &mut dyn OutlineBuilder) {
+    // SVG glyphs are in font units, with the Y axis pointing down
+    let transform = path.abs_transform();
+    let map = |mut p: Point| { transform.map_point(&mut p); (p.x, -p.y) };
+
+    for segment in path.data().segments() {
+        match segment {
+            PathSegment::MoveTo(p) => { let (x, y) = map(p); builder.move_to(x, y); }
+            PathSegment::LineTo(p) => { let (x, y) = map(p); builder.line_to(x, y); }
+            PathSegment::QuadTo(c, p) => {
+                let ((xc, yc), (x, y)) = (map(c), map(p));
+                builder.quad_to(xc, yc, x, y);
+            }
+            PathSegment::CubicTo(c0, c1, p) => {
+                let ((xc0, yc0), (xc1, yc1), (x, y)) = (map(c0), map(c1), map(p));
+                builder.curve_to(xc0, yc0, xc1, yc1, x, y);
+            }
+            PathSegment::Close => builder.close(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_font::{put16, put32, FontBuilder}, BoundingBox, Config, MeshGenerator, Units,
+    };
+
+    /// A font with a glyph that has no outline but a rectangle in the `SVG` table, in a document
+    /// that also holds a larger rectangle for another glyph.
+    fn svg_font() -> Vec<u8> {
+        let mut font = FontBuilder::new();
+        let id = font.glyph(Some('■'), 600, vec![]);
+        let document = format!(concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">"#,
+            r#"<rect id="glyph{}" x="100" y="-700" width="400" height="700"/>"#,
+            r#"<rect id="glyph{}" x="0" y="-1000" width="1000" height="1000"/>"#,
+            "</svg>",
+        ), id, id + 1).into_bytes();
+
+        // a header, then a list of one document covering the glyph
+        let mut svg = Vec::new();
+        put16(&mut svg, 0);
+        put32(&mut svg, 10);
+        put32(&mut svg, 0);
+        put16(&mut svg, 1);
+        for v in [id, id] { put16(&mut svg, v); }
+        put32(&mut svg, 14);
+        put32(&mut svg, document.len() as u32);
+        svg.extend(document);
+        font.table(b"SVG ", svg);
+        font.build()
+    }
+
+    #[test]
+    fn svg_glyph_is_meshed() {
+        let data = svg_font();
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+        let config = Config{units: Units::FontUnits, extrude: None, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let mesh = generator.generate_mesh(face.glyph_index('■').unwrap()).unwrap();
+
+        // only the glyph's own element, with Y flipped up
+        assert_eq!(mesh.bbox, BoundingBox{mins: [100., 0., 0.], maxs: [500., 700., 0.]});
+        assert_eq!(mesh.indices.len(), 6);
+    }
+}
@@ -0,0 +1,155 @@
+//! Exporting outlines and flat meshes as SVG, for debugging tessellation.
+
+use {
+    crate::{BoundingBox, Error, FillRule, GlyphId, Mesh, MeshGenerator, Result},
+    std::{fmt::Write as _, io::{self, Write}},
+};
+
+impl Mesh {
+    /// Writes this mesh's triangles as SVG polygons, seen from +Z, to inspect how a flat mesh
+    /// was tessellated.
+    ///
+    /// Triangles wound counter-clockwise, which face the viewer, are filled blue, and those
+    /// wound clockwise are filled red, so winding problems stand out. Every edge is outlined.
+    /// Extruded meshes are drawn too, but their side walls are seen edge-on.
+    ///
+    /// Arguments:
+    /// * `w`: The writer the SVG document is written to.
+    pub fn write_svg<W: Write>(&self, mut w: W) -> io::Result<()> {
+        let mut out = svg_header(&BoundingBox::from_points(&self.vertices));
+        out += "<style>polygon{stroke:black;stroke-width:1px;vector-effect:non-scaling-stroke}";
+        out += ".front{fill:#8cf}.back{fill:#f88}</style>\n";
+        for tri in self.indices.as_chunks::<3>().0 {
+            let [a, b, c] = tri.map(|i| self.vertices[i as usize]);
+            let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+            let class = if area < 0. {"back"} else {"front"};
+            writeln!(
+                out,
+                r#"<polygon class="{class}" points="{:?},{:?} {:?},{:?} {:?},{:?}"/>"#,
+                a[0], a[1], b[0], b[1], c[0], c[1],
+            ).unwrap();
+        }
+        out += "</g>\n</svg>\n";
+        w.write_all(out.as_bytes())
+    }
+}
+
+impl<'face> MeshGenerator<'face> {
+    /// Writes the flattened outline of `glyph` as an SVG path, as it is passed to the
+    /// tessellator, to check the [Config](crate::Config)'s `tolerance` and the outline's
+    /// winding.
+    ///
+    /// The outline is adjusted by the config's `embolden`, `slant` and winding options, and
+    /// filled by its `fill_rule`. Each point of the flattened contours is marked with a dot.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be written.
+    /// * `w`: The writer the SVG document is written to.
+    ///
+    /// Returns:
+    /// An empty [Result] if successful, otherwise an [Error::Io].
+    pub fn write_glyph_svg<W: Write>(&self, glyph: GlyphId, mut w: W) -> Result<()> {
+        let Some(mut outline) = self.outline(glyph, self.config.tolerance) else {
+            return w.write_all(svg_header(&BoundingBox::default()).as_bytes())
+                .and_then(|_| w.write_all(b"</g>\n</svg>\n"))
+                .map_err(Error::Io);
+        };
+        outline.adjust(&self.config);
+
+        let points: Vec<[f32; 3]> = outline.contours.iter().flatten()
+            .map(|p| [p.x, p.y, 0.])
+            .collect();
+        let mut out = svg_header(&BoundingBox::from_points(&points));
+        let fill_rule = match self.config.fill_rule {
+            FillRule::NonZero => "nonzero",
+            FillRule::EvenOdd => "evenodd",
+        };
+        let mut path = String::new();
+        for contour in &outline.contours {
+            for (i, p) in contour.iter().enumerate() {
+                write!(path, "{}{:?},{:?} ", if i == 0 {"M"} else {"L"}, p.x, p.y).unwrap();
+            }
+            path += "Z ";
+        }
+        writeln!(
+            out,
+            concat!(
+                r##"<path d="{}" fill="#8cf" fill-opacity="0.5" fill-rule="{}" stroke="black" "##,
+                r#"stroke-width="1px" vector-effect="non-scaling-stroke"/>"#,
+            ),
+            path.trim_end(), fill_rule,
+        ).unwrap();
+
+        // the dots are sized to the glyph, as strokes can't size circles
+        let BoundingBox{mins, maxs} = BoundingBox::from_points(&points);
+        let radius = 0.005 * (maxs[0] - mins[0]).max(maxs[1] - mins[1]);
+        for p in outline.contours.iter().flatten() {
+            writeln!(out, r#"<circle cx="{:?}" cy="{:?}" r="{radius:?}"/>"#, p.x, p.y).unwrap();
+        }
+        out += "</g>\n</svg>\n";
+        w.write_all(out.as_bytes()).map_err(Error::Io)
+    }
+}
+
+/// The start of an SVG document framing `bbox`, up to an open group that flips Y up.
+fn svg_header(bbox: &BoundingBox) -> String {
+    let BoundingBox{mins, maxs} = *bbox;
+    let margin = 0.05 * (maxs[0] - mins[0]).max(maxs[1] - mins[1]);
+    let [x, y] = [mins[0] - margin, -maxs[1] - margin];
+    let [width, height] = [maxs[0] - mins[0], maxs[1] - mins[1]].map(|s| s + 2. * margin);
+    format!(concat!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{:?} {:?} {:?} {:?}">"#, "\n",
+        r#"<g transform="scale(1,-1)">"#, "\n",
+    ), x, y, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_face, Config, Mesh, MeshGenerator};
+
+    #[test]
+    fn svg_structure() {
+        let face = test_face();
+        let config = Config{tolerance: 0.005, extrude: None, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let write = |mesh: &Mesh| {
+            let mut svg = Vec::new();
+            mesh.write_svg(&mut svg).unwrap();
+            let svg = String::from_utf8(svg).unwrap();
+            assert!(svg.starts_with("<svg ") && svg.ends_with("</g>\n</svg>\n"));
+            svg
+        };
+
+        // one polygon per triangle, filled by its winding
+        let mut mesh = generator.generate_text("Bo").unwrap();
+        let triangles = mesh.indices.len() / 3;
+        let svg = write(&mesh);
+        assert_eq!(svg.matches("<polygon ").count(), triangles);
+        assert_eq!(svg.matches(r#"class="front""#).count(), triangles);
+        // reversed, all but the slivers of no area that the tessellator leaves face away
+        let slivers = mesh.indices.as_chunks::<3>().0.iter()
+            .filter(|tri| {
+                let [a, b, c] = tri.map(|i| mesh.vertices[i as usize]);
+                (b[0] - a[0]) * (c[1] - a[1]) == (b[1] - a[1]) * (c[0] - a[0])
+            })
+            .count();
+        for tri in mesh.indices.as_chunks_mut::<3>().0 { tri.swap(1, 2); }
+        assert_eq!(write(&mesh).matches(r#"class="back""#).count(), triangles - slivers);
+
+        let space = generator.generate_text(" ").unwrap();
+        assert!(!write(&space).contains("<polygon"));
+
+        // a path with a subpath per contour, and a dot at each point
+        let mut svg = Vec::new();
+        generator.write_glyph_svg(face.glyph_index('B').unwrap(), &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert_eq!(svg.matches("<path ").count(), 1);
+        assert_eq!(svg.matches('M').count(), 3);
+        assert_eq!(svg.matches(" L").count() + 3, svg.matches("<circle ").count());
+
+        let mut svg = Vec::new();
+        generator.write_glyph_svg(face.glyph_index(' ').unwrap(), &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.ends_with("</g>\n</svg>\n") && !svg.contains("<path"));
+    }
+}
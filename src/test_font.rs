@@ -0,0 +1,292 @@
+//! Building small TrueType fonts for tests, to exercise the tables that the test font lacks.
+
+/// A TrueType font under construction, with glyphs made of straight-edged or quadratic contours.
+/// Glyph 0 is an empty `.notdef`.
+pub(crate) struct FontBuilder {
+    glyphs: Vec<(u16, Vec<Contour>)>,
+    chars: Vec<(char, u16)>,
+    tables: Vec<([u8; 4], Vec<u8>)>,
+}
+
+/// A contour of points, each with whether it is on the curve, as in the `glyf` table.
+pub(crate) type Contour = Vec<([i16; 2], bool)>;
+
+/// The units per em of built fonts, which are also their ascender less their descender.
+pub(crate) const UNITS_PER_EM: u16 = 1000;
+
+impl FontBuilder {
+    pub fn new() -> Self {
+        Self{glyphs: vec![(UNITS_PER_EM / 2, Vec::new())], chars: Vec::new(), tables: Vec::new()}
+    }
+
+    /// Adds a glyph with the given horizontal `advance` and `contours` of on-curve points,
+    /// mapped from `c` if given, and returns its id.
+    pub fn glyph(&mut self, c: Option<char>, advance: u16, contours: Vec<Vec<[i16; 2]>>) -> u16 {
+        let contours = contours.into_iter()
+            .map(|contour| contour.into_iter().map(|p| (p, true)).collect())
+            .collect();
+        self.curved_glyph(c, advance, contours)
+    }
+
+    /// Adds a glyph with the given horizontal `advance` and `contours` of on-curve and off-curve
+    /// points, mapped from `c` if given, and returns its id.
+    pub fn curved_glyph(&mut self, c: Option<char>, advance: u16, contours: Vec<Contour>) -> u16 {
+        let id = self.glyphs.len() as u16;
+        self.glyphs.push((advance, contours));
+        self.chars.extend(c.map(|c| (c, id)));
+        id
+    }
+
+    /// Adds a table of the given raw `data`.
+    pub fn table(&mut self, tag: &[u8; 4], data: Vec<u8>) {
+        self.tables.push((*tag, data));
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let points = || self.glyphs.iter().flat_map(|(_, contours)| on_curve(contours));
+        let [x_min, y_min, x_max, y_max] = bounds(points()).unwrap_or_default();
+
+        let mut head = Vec::new();
+        put32(&mut head, 0x0001_0000);
+        put32(&mut head, 0x0001_0000);
+        put32(&mut head, 0);
+        put32(&mut head, 0x5F0F_3CF5);
+        put16(&mut head, 0);
+        put16(&mut head, UNITS_PER_EM);
+        head.extend([0; 16]);
+        for v in [x_min, y_min, x_max, y_max] { put16(&mut head, v as u16); }
+        put16(&mut head, 0);
+        put16(&mut head, 8);
+        put16(&mut head, 2);
+        put16(&mut head, 1); // long loca offsets
+        put16(&mut head, 0);
+
+        let mut hhea = Vec::new();
+        put32(&mut hhea, 0x0001_0000);
+        put16(&mut hhea, 800);
+        put16(&mut hhea, (-200i16) as u16);
+        put16(&mut hhea, 0);
+        put16(&mut hhea, self.glyphs.iter().map(|g| g.0).max().unwrap_or(0));
+        hhea.extend([0; 4]);
+        put16(&mut hhea, x_max as u16);
+        put16(&mut hhea, 1);
+        hhea.extend([0; 14]);
+        put16(&mut hhea, self.glyphs.len() as u16);
+
+        let mut maxp = Vec::new();
+        put32(&mut maxp, 0x0000_5000);
+        put16(&mut maxp, self.glyphs.len() as u16);
+
+        let mut hmtx = Vec::new();
+        let mut glyf = Vec::new();
+        let mut loca = Vec::new();
+        for (advance, contours) in &self.glyphs {
+            let lsb = bounds(on_curve(contours)).map_or(0, |b| b[0]);
+            put16(&mut hmtx, *advance);
+            put16(&mut hmtx, lsb as u16);
+            put32(&mut loca, glyf.len() as u32);
+            glyf.extend(glyph_data(contours));
+        }
+        put32(&mut loca, glyf.len() as u32);
+
+        let mut chars = self.chars.clone();
+        chars.sort();
+        let mut cmap = Vec::new();
+        put16(&mut cmap, 0);
+        put16(&mut cmap, 1);
+        put16(&mut cmap, 3);
+        put16(&mut cmap, 10);
+        put32(&mut cmap, 12);
+        put16(&mut cmap, 12);
+        put16(&mut cmap, 0);
+        put32(&mut cmap, 16 + 12 * chars.len() as u32);
+        put32(&mut cmap, 0);
+        put32(&mut cmap, chars.len() as u32);
+        for (c, id) in chars {
+            put32(&mut cmap, c as u32);
+            put32(&mut cmap, c as u32);
+            put32(&mut cmap, id as u32);
+        }
+
+        let mut tables = vec![
+            (*b"head", head), (*b"hhea", hhea), (*b"maxp", maxp), (*b"hmtx", hmtx),
+            (*b"loca", loca), (*b"glyf", glyf), (*b"cmap", cmap),
+        ];
+        tables.extend(self.tables.iter().cloned());
+        // faces look their tables up by binary search
+        tables.sort_by_key(|(tag, _)| *tag);
+
+        let mut font = Vec::new();
+        put32(&mut font, 0x0001_0000);
+        put16(&mut font, tables.len() as u16);
+        font.extend([0; 6]);
+        let mut offset = 12 + 16 * tables.len();
+        for (tag, data) in &tables {
+            font.extend(tag);
+            put32(&mut font, 0);
+            put32(&mut font, offset as u32);
+            put32(&mut font, data.len() as u32);
+            offset += data.len().next_multiple_of(4);
+        }
+        for (_, data) in &tables {
+            font.extend(data);
+            font.resize(font.len().next_multiple_of(4), 0);
+        }
+        font
+    }
+}
+
+/// The font that most tests mesh glyphs from: simple sans-serif shapes for the characters
+/// ` &.ABHILOTVaghiouxy`, with holes, curves, overlapping contours, descenders and a
+/// multi-contour `i` whose stem comes before its dot.
+pub(crate) fn test_font() -> Vec<u8> {
+    let straight = |points: Vec<[i16; 2]>| -> Contour {
+        points.into_iter().map(|p| (p, true)).collect()
+    };
+
+    let mut font = FontBuilder::new();
+    font.glyph(Some(' '), 300, vec![]);
+    font.glyph(Some('.'), 300, vec![rect(100, 0, 200, 100)]);
+    font.curved_glyph(Some('&'), 650, vec![
+        ellipse(250, 520, 140, 150, false),
+        ellipse(250, 520, 60, 70, true),
+        ellipse(260, 200, 240, 200, false),
+        ellipse(260, 200, 140, 110, true),
+        straight(vec![[400, 250], [480, 320], [650, 80], [570, 0]]),
+    ]);
+    font.glyph(Some('A'), 600, vec![
+        vec![[0, 0], [250, 700], [350, 700], [600, 0]],
+        vec![[200, 250], [400, 250], [300, 550]],
+    ]);
+    font.glyph(Some('B'), 600, vec![
+        vec![[0, 0], [0, 700], [450, 700], [550, 600], [550, 450], [500, 375], [550, 300],
+            [550, 100], [450, 0]],
+        hole(100, 100, 450, 300),
+        hole(100, 450, 450, 600),
+    ]);
+    font.glyph(Some('H'), 600, vec![vec![
+        [0, 0], [0, 700], [100, 700], [100, 400], [500, 400], [500, 700], [600, 700], [600, 0],
+        [500, 0], [500, 300], [100, 300], [100, 0],
+    ]]);
+    font.glyph(Some('I'), 600, vec![rect(250, 0, 350, 700)]);
+    font.glyph(Some('L'), 550, vec![vec![[0, 0], [0, 700], [100, 700], [100, 100], [500, 100],
+        [500, 0]]]);
+    font.curved_glyph(Some('O'), 650, vec![
+        ellipse(325, 350, 300, 350, false),
+        ellipse(325, 350, 200, 250, true),
+    ]);
+    font.glyph(Some('T'), 600, vec![vec![
+        [0, 700], [600, 700], [600, 600], [350, 600], [350, 0], [250, 0], [250, 600], [0, 600],
+    ]]);
+    font.glyph(Some('V'), 600, vec![vec![
+        [0, 700], [100, 700], [300, 100], [500, 700], [600, 700], [350, 0], [250, 0],
+    ]]);
+    font.curved_glyph(Some('a'), 500, vec![
+        ellipse(230, 180, 200, 180, false),
+        ellipse(230, 180, 100, 80, true),
+        straight(rect(360, 0, 440, 500)),
+    ]);
+    font.curved_glyph(Some('g'), 500, vec![
+        ellipse(250, 250, 200, 200, false),
+        ellipse(250, 250, 100, 100, true),
+        straight(vec![[50, -200], [50, -120], [350, -120], [350, 300], [450, 300], [450, -200]]),
+    ]);
+    font.glyph(Some('h'), 500, vec![vec![
+        [0, 0], [0, 750], [100, 750], [100, 450], [400, 450], [400, 0], [300, 0], [300, 350],
+        [100, 350], [100, 0],
+    ]]);
+    font.glyph(Some('i'), 300, vec![rect(100, 0, 200, 500), rect(100, 600, 200, 700)]);
+    font.curved_glyph(Some('o'), 500, vec![
+        ellipse(250, 250, 250, 250, false),
+        ellipse(250, 250, 150, 150, true),
+    ]);
+    font.glyph(Some('u'), 500, vec![vec![
+        [0, 500], [100, 500], [100, 100], [300, 100], [300, 500], [400, 500], [400, 0], [0, 0],
+    ]]);
+    font.glyph(Some('x'), 400, vec![vec![
+        [0, 0], [150, 250], [0, 500], [100, 500], [200, 320], [300, 500], [400, 500],
+        [250, 250], [400, 0], [300, 0], [200, 180], [100, 0],
+    ]]);
+    font.glyph(Some('y'), 400, vec![vec![
+        [0, 500], [100, 500], [200, 150], [300, 500], [400, 500], [150, -200], [50, -200],
+        [150, 50],
+    ]]);
+    font.build()
+}
+
+/// A rectangle from `[x0, y0]` to `[x1, y1]`, wound clockwise as TrueType outer contours are.
+pub(crate) fn rect(x0: i16, y0: i16, x1: i16, y1: i16) -> Vec<[i16; 2]> {
+    vec![[x0, y0], [x0, y1], [x1, y1], [x1, y0]]
+}
+
+/// An ellipse centred on `[cx, cy]` with radii `rx` and `ry`, of eight quadratic arcs, wound
+/// clockwise as an outer contour or counter-clockwise as a hole.
+pub(crate) fn ellipse(cx: i16, cy: i16, rx: i16, ry: i16, hole: bool) -> Contour {
+    // each arc's control point lies where the tangents at its ends meet
+    let reach = 1. / std::f32::consts::FRAC_PI_8.cos();
+    let sign = if hole {1.} else {-1.};
+    (0 .. 16)
+        .map(|i| {
+            let angle = sign * i as f32 * std::f32::consts::FRAC_PI_8;
+            let r = if i % 2 == 0 {1.} else {reach};
+            let x = cx as f32 + r * rx as f32 * angle.cos();
+            let y = cy as f32 + r * ry as f32 * angle.sin();
+            ([x.round() as i16, y.round() as i16], i % 2 == 0)
+        })
+        .collect()
+}
+
+/// A rectangle from `[x0, y0]` to `[x1, y1]`, wound counter-clockwise as TrueType holes are.
+pub(crate) fn hole(x0: i16, y0: i16, x1: i16, y1: i16) -> Vec<[i16; 2]> {
+    vec![[x0, y0], [x1, y0], [x1, y1], [x0, y1]]
+}
+
+pub(crate) fn put16(data: &mut Vec<u8>, v: u16) {
+    data.extend(v.to_be_bytes());
+}
+
+pub(crate) fn put32(data: &mut Vec<u8>, v: u32) {
+    data.extend(v.to_be_bytes());
+}
+
+/// The on-curve points of `contours`, which bound the outline of ellipses and polygons.
+fn on_curve(contours: &[Contour]) -> impl Iterator<Item = &[i16; 2]> {
+    contours.iter().flatten().filter(|(_, on)| *on).map(|(p, _)| p)
+}
+
+fn bounds<'a>(points: impl Iterator<Item = &'a [i16; 2]>) -> Option<[i16; 4]> {
+    points.fold(None, |b, &[x, y]| Some(match b {
+        None => [x, y, x, y],
+        Some([x0, y0, x1, y1]) => [x0.min(x), y0.min(y), x1.max(x), y1.max(y)],
+    }))
+}
+
+/// The `glyf` entry of a simple glyph with the given `contours`, padded to an even length.
+fn glyph_data(contours: &[Contour]) -> Vec<u8> {
+    let Some([x_min, y_min, x_max, y_max]) = bounds(on_curve(contours)) else {
+        return Vec::new();
+    };
+
+    let mut data = Vec::new();
+    put16(&mut data, contours.len() as u16);
+    for v in [x_min, y_min, x_max, y_max] { put16(&mut data, v as u16); }
+    let mut end = 0;
+    for contour in contours {
+        end += contour.len() as u16;
+        put16(&mut data, end - 1);
+    }
+    put16(&mut data, 0);
+
+    // every point has both coordinates as 16-bit deltas
+    let points: Vec<([i16; 2], bool)> = contours.iter().flatten().copied().collect();
+    data.extend(points.iter().map(|&(_, on)| on as u8));
+    for axis in 0 .. 2 {
+        let mut prev = 0;
+        for (p, _) in &points {
+            put16(&mut data, p[axis].wrapping_sub(prev) as u16);
+            prev = p[axis];
+        }
+    }
+    data.resize(data.len().next_multiple_of(2), 0);
+    data
+}
@@ -0,0 +1,1140 @@
+//! Laying out and meshing strings of text.
+
+use {
+    crate::{
+        build_mesh, cross, length, planar_uv, sub, BoundingBox, Config, Error, FaceFlags, GlyphId,
+        Mesh, MeshGenerator, MissingGlyph, OutlineBuilder, Result, SubmeshKind, UvMapping, TOFU,
+    },
+    std::{
+        borrow::Cow,
+        collections::{hash_map::Entry, HashMap},
+    },
+    ttf_parser::{
+        gpos::{PairAdjustment, PositioningSubtable},
+        gsub::{SingleSubstitution, SubstitutionSubtable},
+        opentype_layout::LayoutTable,
+        LineMetrics, Tag,
+    },
+    unicode_bidi::BidiInfo,
+};
+
+/// Options for laying out text over one or more lines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TextLayout {
+    /// Whether to adjust the advance between adjacent glyphs by the face's kerning.
+    pub kerning: bool,
+
+    /// How each line is placed horizontally relative to x = 0.
+    pub align: Align,
+
+    /// If set, lines are wrapped at whitespace so that none is longer than this, in the same units
+    /// as generated meshes. A single word that is longer still gets a line of its own.
+    pub max_width: Option<f32>,
+
+    /// Extra space added between adjacent glyphs, in the same units as generated meshes. Negative
+    /// values tighten the text.
+    pub letter_spacing: f32,
+
+    /// If set, the distance between consecutive lines, in the same units as generated meshes,
+    /// instead of the face's [line_height](MeshGenerator::line_height).
+    pub line_height: Option<f32>,
+
+    /// If set, the spacing of a baseline grid that lines are snapped to, in the same units as
+    /// generated meshes, so that text of different sizes lines up across columns. Each line
+    /// takes up as many rows of the grid as its line height needs, so its baseline lies on the
+    /// first grid line at least one line height below the previous one. In vertical text, the
+    /// grid spaces the columns instead.
+    pub baseline_grid: Option<f32>,
+
+    /// The direction in which glyphs advance along a line.
+    pub writing_mode: WritingMode,
+
+    /// Whether vertical text uses the face's vertical alternates, from the `vert` feature of its
+    /// `GSUB` table, such as rotated brackets and repositioned small kana.
+    pub vertical_alternates: bool,
+
+    /// Whether to underline each line, with a bar placed and sized by the face's underline
+    /// metrics from its `post` table. Bars are meshed like glyphs, so they are extruded to the
+    /// same depth. This has no effect on vertical text.
+    pub underline: bool,
+
+    /// Whether to strike through each line, with a bar placed and sized by the face's strikeout
+    /// metrics from its `OS/2` table, in the same way as `underline`.
+    pub strikethrough: bool,
+
+    /// If set, a plate is generated behind the text, such as for a name tag. Its triangles follow
+    /// those of the text, as a [SubmeshKind::Plate] submesh.
+    pub plate: Option<Plate>,
+
+    /// Whether [layout_text](MeshGenerator::layout_text) returns the bounding box of each glyph,
+    /// for hit-testing.
+    pub glyph_bounds: bool,
+
+    /// Whether [layout_text_glyphs](MeshGenerator::layout_text_glyphs) returns the mesh of each
+    /// glyph already moved to its position in the text, so that the meshes can be concatenated
+    /// as they are, rather than at the glyph's own origin.
+    pub bake_positions: bool,
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self {
+            kerning: true,
+            align: Align::Left,
+            max_width: None,
+            letter_spacing: 0.,
+            line_height: None,
+            baseline_grid: None,
+            writing_mode: WritingMode::Horizontal,
+            vertical_alternates: true,
+            underline: false,
+            strikethrough: false,
+            plate: None,
+            glyph_bounds: false,
+            bake_positions: false,
+        }
+    }
+}
+
+/// The alignment of lines of text. Lines are measured by the advances of their glyphs.
+///
+/// In vertical text, lines are aligned along the Y axis instead, with the start of a line at its
+/// top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Align {
+    /// Each line starts at x = 0.
+    #[default]
+    Left,
+
+    /// Each line is centred on x = 0.
+    Center,
+
+    /// Each line ends at x = 0.
+    Right,
+}
+
+/// The direction in which glyphs advance along a line of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WritingMode {
+    /// Glyphs advance rightwards along a line, and lines advance downwards.
+    #[default]
+    Horizontal,
+
+    /// Glyphs advance downwards along a column, centred on it, and columns advance leftwards, as
+    /// in traditional CJK text. This uses the face's vertical metrics, from its `vmtx` and `VORG`
+    /// tables, where it has them. Kerning is not applied.
+    Vertical,
+}
+
+/// A rounded rectangle behind laid out text, sized to the text's bounding box.
+///
+/// The plate is meshed like a glyph, with the generator's normals and texture coordinates; its
+/// caps are mapped to their own bounds rather than the text's. It is always a solid slab with
+/// both caps and straight sides: the [Config]'s stroke, bevel, profile, taper, twist, oblique
+/// extrusion, synthetic bold and italics, and choice of faces only shape the glyphs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plate {
+    /// The margin between the text's bounding box and the edge of the plate, in the same units
+    /// as generated meshes.
+    pub padding: f32,
+
+    /// The radius of the plate's corners, limited to half its shorter side. Zero gives square
+    /// corners.
+    pub corner_radius: f32,
+
+    /// The thickness of the plate, extending back along the Z axis from the text's rear cap, or
+    /// `None` for a flat quad facing +Z in the plane of the rear cap. For flat text, that is the
+    /// plane of the text itself, so the plate must be drawn first.
+    pub depth: Option<f32>,
+}
+
+/// A [Mesh] of laid out text, with the bounds of each of its lines.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextMesh {
+    /// The mesh of the whole text.
+    pub mesh: Mesh,
+    /// The bounding box of each line, in order. An empty line has an empty box at its origin.
+    pub lines: Vec<BoundingBox>,
+    /// If the layout asked for them, each character drawn with the bounding box of its glyph's
+    /// mesh where it was placed, in visual order, so that points can be mapped to characters.
+    /// Glyphs without any triangles, such as spaces, are left out.
+    pub glyphs: Vec<(char, BoundingBox)>,
+}
+
+/// Laid out text as the distinct meshes of its glyphs and the places they appear, as generated
+/// by [MeshGenerator::layout_text_instanced].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InstancedText {
+    /// The mesh of each distinct glyph in the text, at its own origin.
+    pub meshes: HashMap<GlyphId, Mesh>,
+    /// Each glyph to be drawn, with the transform that moves its mesh into place, as a
+    /// column-major 4x4 matrix.
+    pub instances: Vec<(GlyphId, [[f32; 4]; 4])>,
+}
+
+/// A glyph of laid out text, as generated by [MeshGenerator::layout_text_glyphs].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlacedGlyph {
+    /// The glyph that was meshed.
+    pub glyph_id: GlyphId,
+    /// The position of the glyph's origin in the text.
+    pub position: [f32; 3],
+    /// The mesh of the glyph, at `position` if the layout's `bake_positions` is set, and
+    /// otherwise at its own origin.
+    pub mesh: Mesh,
+}
+
+/// The size of laid out text, as measured by [MeshGenerator::measure].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextMetrics {
+    /// The length of the longest line, by the advances of its glyphs.
+    pub width: f32,
+    /// The number of lines, after wrapping.
+    pub line_count: usize,
+    /// The length of each line, by the advances of its glyphs, in order.
+    pub line_widths: Vec<f32>,
+    /// The bounding box that the text's mesh would have.
+    pub bbox: BoundingBox,
+}
+
+/// A glyph's [Mesh], with the horizontal metrics needed to place it, as generated by
+/// [MeshGenerator::generate_glyph_mesh].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GlyphMesh {
+    /// The mesh of the glyph, as [MeshGenerator::generate_mesh] generates it.
+    pub mesh: Mesh,
+    /// The distance from the glyph's origin to the origin of the next glyph, in the same units as
+    /// generated meshes.
+    pub advance: f32,
+    /// The distance from the glyph's origin to the left edge of its outline, in the same units as
+    /// generated meshes.
+    pub bearing: f32,
+    /// The glyph that was meshed.
+    pub glyph_id: GlyphId,
+}
+
+/// Glyphs placed along a line of text, each with the character it was mapped from and its
+/// position.
+type Placement = Vec<(char, GlyphId, [f32; 2])>;
+
+/// A single line of text meshed from the origin, by [MeshGenerator::mesh_line].
+struct LineMesh {
+    mesh: Mesh,
+    /// The indices of the cap vertices, if cap UVs are mapped to the text bounds.
+    caps: Vec<usize>,
+    /// The bounding box of each glyph with triangles, if the layout asked for them.
+    glyphs: Vec<(char, BoundingBox)>,
+    length: f32,
+}
+
+impl<'face> MeshGenerator<'face> {
+    /// Generates a single [Mesh] for some text, laid out with the default [TextLayout]. See
+    /// [layout_text](Self::layout_text).
+    ///
+    /// Arguments:
+    /// * `text`: The text to be meshed.
+    ///
+    /// Returns:
+    /// A [Result] containing the [Mesh] if successful, otherwise an [Error].
+    pub fn generate_text(&self, text: &str) -> Result<Mesh> {
+        self.layout_text(text, &TextLayout::default()).map(|laid_out| laid_out.mesh)
+    }
+
+    /// Generates a single [Mesh] for some text, with the bounds of each of its lines.
+    ///
+    /// Each `\n` starts a new line, with its baseline one line height below the previous one: the
+    /// layout's `line_height` if set, or else the face's [line_height](Self::line_height); the
+    /// first line's baseline passes through the origin. Within a line, each character is mapped to
+    /// its glyph, or to the face's missing glyph if it has none, and placed after the previous one
+    /// according to the face's horizontal advances, the letter spacing and, if enabled, its
+    /// kerning. Other control characters are skipped. Lines are then wrapped and aligned
+    /// horizontally as set in the `layout`.
+    ///
+    /// Mixed left-to-right and right-to-left text is reordered into visual order line by line,
+    /// following the Unicode Bidirectional Algorithm, with each paragraph's direction taken from
+    /// its first strongly directional character.
+    ///
+    /// In [vertical](WritingMode::Vertical) text, the lines are columns running downwards from
+    /// y = 0, with each one line height to the left of the previous one, and the first centred on
+    /// x = 0. Their bounds are returned in the same way.
+    ///
+    /// Arguments:
+    /// * `text`: The text to be meshed.
+    /// * `layout`: The [TextLayout] that should be used.
+    ///
+    /// Returns:
+    /// A [Result] containing the [TextMesh] if successful, otherwise an [Error].
+    pub fn layout_text(&self, text: &str, layout: &TextLayout) -> Result<TextMesh> {
+        let text_bounds = self.config.uv_mapping == Some(UvMapping::TextBounds);
+        let mut mesh = Mesh::default();
+        let mut lines = Vec::new();
+        let mut caps = Vec::new();
+        let mut glyphs = Vec::new();
+        for (i, line) in self.wrapped_lines(text, layout)?.iter().enumerate() {
+            let LineMesh{mesh: line_mesh, caps: line_caps, glyphs: line_glyphs, length}
+                = self.mesh_line(line, layout)?;
+            let origin = self.line_origin(layout, i, length);
+
+            let base = mesh.vertices.len();
+            caps.extend(line_caps.into_iter().map(|i| base + i));
+            glyphs.extend(line_glyphs.into_iter().map(|(c, bbox)| (c, bbox.translated(origin))));
+            lines.push(match line_mesh.vertices.is_empty() {
+                true  => BoundingBox::new(origin, origin),
+                false => line_mesh.bbox.translated(origin),
+            });
+            mesh.append_translated(&line_mesh, origin);
+        }
+
+        if text_bounds {
+            for i in caps { mesh.uvs[i] = planar_uv(mesh.vertices[i], &mesh.bbox); }
+        }
+
+        if let Some(plate) = layout.plate {
+            // the plate is one submesh, after the parts of the text
+            let mut plate_mesh = self.mesh_plate(plate, &mesh.bbox)?;
+            let n_indices = plate_mesh.indices.len() as u32;
+            plate_mesh.submeshes = vec![(SubmeshKind::Plate, 0 .. n_indices)];
+            let depth = plate.depth.unwrap_or(0.);
+            let rear = -self.config.half_depth();
+            mesh.append_translated(&plate_mesh, [0., 0., rear - depth / 2.]);
+        }
+
+        let coordinates = self.config.coordinates;
+        let mesh = mesh.in_coordinates(coordinates);
+        let lines = lines.iter().map(|line| line.in_coordinates(coordinates)).collect();
+        let glyphs = glyphs.into_iter().map(|(c, bbox)| (c, bbox.in_coordinates(coordinates)));
+        Ok(TextMesh{mesh, lines, glyphs: glyphs.collect()})
+    }
+
+    /// Lays out some text as [layout_text](Self::layout_text) does, but returns each distinct
+    /// glyph's mesh once, with a transform for each place it appears, instead of one merged
+    /// mesh. Long, repetitive text, such as a console or scoreboard, can then be drawn with GPU
+    /// instancing.
+    ///
+    /// Glyphs without any triangles, such as spaces, get no instances. Underlines,
+    /// strikethroughs and plates aren't generated, and UVs mapped to the text's bounds fall
+    /// back to each glyph's own bounds, since instances share their glyph's mesh.
+    ///
+    /// Arguments:
+    /// * `text`: The text to be meshed.
+    /// * `layout`: The [TextLayout] that should be used.
+    ///
+    /// Returns:
+    /// A [Result] containing the [InstancedText] if successful, otherwise an [Error].
+    pub fn layout_text_instanced(&self, text: &str, layout: &TextLayout) -> Result<InstancedText> {
+        let mut instanced = InstancedText::default();
+        let coordinates = self.config.coordinates;
+        for (i, line) in self.wrapped_lines(text, layout)?.iter().enumerate() {
+            let (placed, length) = self.place_glyphs(line, layout)?;
+            let [ox, oy, oz] = self.line_origin(layout, i, length);
+            for (_, glyph, [x, y]) in placed {
+                let mesh = match instanced.meshes.entry(glyph) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(self.generate_mesh(glyph)?),
+                };
+                if mesh.vertices.is_empty() { continue; }
+
+                let [tx, ty, tz] = coordinates.map([ox + x, oy + y, oz]);
+                let columns = [
+                    [1., 0., 0., 0.],
+                    [0., 1., 0., 0.],
+                    [0., 0., 1., 0.],
+                    [tx, ty, tz, 1.],
+                ];
+                instanced.instances.push((glyph, columns));
+            }
+        }
+        Ok(instanced)
+    }
+
+    /// Lays out some text as [layout_text](Self::layout_text) does, but returns the mesh of each
+    /// glyph separately, in the order they are placed, along with its position. With the
+    /// layout's `bake_positions` set, each mesh is moved to its position already, so the meshes
+    /// can simply be concatenated.
+    ///
+    /// Glyphs without any triangles, such as spaces, are included with empty meshes.
+    /// Underlines, strikethroughs and plates aren't generated, and UVs mapped to the text's
+    /// bounds fall back to each glyph's own bounds.
+    ///
+    /// Arguments:
+    /// * `text`: The text to be meshed.
+    /// * `layout`: The [TextLayout] that should be used.
+    ///
+    /// Returns:
+    /// A [Result] containing the [PlacedGlyph]s if successful, otherwise an [Error].
+    pub fn layout_text_glyphs(&self, text: &str, layout: &TextLayout) -> Result<Vec<PlacedGlyph>> {
+        let coordinates = self.config.coordinates;
+        self.layout_glyphs(text, layout)?.into_iter()
+            .map(|(_, glyph, [x, y])| {
+                let mut mesh = self.cached_glyph(glyph)?;
+                if layout.bake_positions {
+                    let mut baked = Mesh::default();
+                    baked.append_translated(&mesh, [x, y, 0.]);
+                    mesh = baked;
+                }
+                let position = coordinates.map([x, y, 0.]);
+                Ok(PlacedGlyph{glyph_id: glyph, position, mesh: mesh.in_coordinates(coordinates)})
+            })
+            .collect()
+    }
+
+    /// The lines of `text` in visual order, split at line breaks and wrapped.
+    fn wrapped_lines<'t>(&self, text: &'t str, layout: &TextLayout) -> Result<Vec<Cow<'t, str>>> {
+        let mut wrapped = Vec::new();
+        for paragraph in text.split('\n') {
+            wrapped.extend(self.visual_lines(paragraph, layout)?);
+        }
+        Ok(wrapped)
+    }
+
+    /// The glyphs of `text`, laid out as [layout_text](Self::layout_text) places them, with the
+    /// origin of each.
+    pub(crate) fn layout_glyphs(&self, text: &str, layout: &TextLayout) -> Result<Placement> {
+        let mut glyphs = Vec::new();
+        for (i, line) in self.wrapped_lines(text, layout)?.iter().enumerate() {
+            let (placed, length) = self.place_glyphs(line, layout)?;
+            let [ox, oy, _] = self.line_origin(layout, i, length);
+            glyphs.extend(placed.into_iter()
+                .map(|(c, glyph, [x, y])| (c, glyph, [ox + x, oy + y])));
+        }
+        Ok(glyphs)
+    }
+
+    /// The distance between consecutive lines of a layout.
+    fn line_pitch(&self, layout: &TextLayout) -> f32 {
+        let line_height = layout.line_height.unwrap_or_else(|| self.line_height());
+        match layout.baseline_grid.filter(|&grid| grid > 0.) {
+            // allow for rounding in line heights that are a whole number of rows
+            Some(grid) => (line_height / grid - 1e-4).ceil().max(1.) * grid,
+            None => line_height,
+        }
+    }
+
+    /// The origin of the `i`th line of a layout, which is `length` long.
+    fn line_origin(&self, layout: &TextLayout, i: usize, length: f32) -> [f32; 3] {
+        let line_height = self.line_pitch(layout);
+        let along = match layout.align {
+            Align::Left   => 0.,
+            Align::Center => 0.5 * length,
+            Align::Right  => length,
+        };
+        let across = i as f32 * line_height;
+        match layout.writing_mode {
+            WritingMode::Horizontal => [-along, -across, 0.],
+            WritingMode::Vertical   => [-across, along, 0.],
+        }
+    }
+
+    /// Meshes a plate around `bbox`, centred on z = 0.
+    fn mesh_plate(&self, plate: Plate, bbox: &BoundingBox) -> Result<Mesh> {
+        let [x0, y0] = [bbox.mins[0] - plate.padding, bbox.mins[1] - plate.padding];
+        let [x1, y1] = [bbox.maxs[0] + plate.padding, bbox.maxs[1] + plate.padding];
+        let r = plate.corner_radius.clamp(0., 0.5 * (x1 - x0).min(y1 - y0));
+
+        // each corner is a quarter circle, approximated by a cubic bézier curve
+        let k = 0.552_284_8 * r;
+        let mut builder = OutlineBuilder::new(1., self.config.tolerance);
+        builder.move_to(x0 + r, y0);
+        builder.line_to(x1 - r, y0);
+        builder.curve_to(x1 - r + k, y0, x1, y0 + r - k, x1, y0 + r);
+        builder.line_to(x1, y1 - r);
+        builder.curve_to(x1, y1 - r + k, x1 - r + k, y1, x1 - r, y1);
+        builder.line_to(x0 + r, y1);
+        builder.curve_to(x0 + r - k, y1, x0, y1 - r + k, x0, y1 - r);
+        builder.line_to(x0, y0 + r);
+        builder.curve_to(x0, y0 + r - k, x0 + r - k, y0, x0 + r, y0);
+        builder.close();
+
+        // the plate is always a solid, straight slab, whatever shapes the glyphs
+        let config = Config {
+            extrude: plate.depth,
+            extrude_direction: [0., 0., -1.],
+            taper: 0.,
+            twist_degrees: 0.,
+            normalize_winding: false,
+            embolden: 0.,
+            slant: 0.,
+            bevel: None,
+            profile: None,
+            stroke: None,
+            contour_depth: None,
+            faces: FaceFlags::default(),
+            ..self.config.clone()
+        };
+        build_mesh(builder, &config, &mut self.tessellator.borrow_mut())
+    }
+
+    /// Meshes a single line of text from the origin.
+    fn mesh_line(&self, line: &str, layout: &TextLayout) -> Result<LineMesh> {
+        let text_bounds = self.config.uv_mapping == Some(UvMapping::TextBounds);
+        let mut mesh = Mesh::default();
+        let mut caps = Vec::new();
+        let mut glyphs = Vec::new();
+        let (placed, length) = self.place_glyphs(line, layout)?;
+        let mut append = |part: &Mesh, offset: [f32; 3]| {
+            if text_bounds {
+                let base = mesh.vertices.len();
+                for (_, range) in part.submeshes.iter().filter(|(k, _)| *k != SubmeshKind::Sides) {
+                    let cap_indices = &part.indices[range.start as usize .. range.end as usize];
+                    caps.extend(cap_indices.iter().map(|&i| base + i as usize));
+                }
+            }
+            mesh.append_translated(part, offset);
+        };
+
+        for (c, glyph, [x, y]) in placed {
+            let glyph_mesh = self.cached_glyph(glyph)?;
+            if layout.glyph_bounds && !glyph_mesh.vertices.is_empty() {
+                glyphs.push((c, glyph_mesh.bbox.translated([x, y, 0.])));
+            }
+            append(&glyph_mesh, [x, y, 0.]);
+        }
+
+        if layout.writing_mode == WritingMode::Horizontal && length > 0. {
+            let bars = [
+                layout.underline.then(|| self.face.underline_metrics()),
+                layout.strikethrough.then(|| self.face.strikeout_metrics()),
+            ];
+            for metrics in bars.into_iter().flatten().flatten() {
+                append(&self.mesh_bar(metrics, length)?, [0.; 3]);
+            }
+        }
+
+        Ok(LineMesh{mesh, caps, glyphs, length})
+    }
+
+    /// Meshes an underline or strikethrough bar running from the origin for `length`, with its
+    /// top edge at the position given by `metrics`.
+    fn mesh_bar(&self, metrics: LineMetrics, length: f32) -> Result<Mesh> {
+        let top = metrics.position as f32 * self.scale;
+        let bottom = top - metrics.thickness as f32 * self.scale;
+        let mut builder = OutlineBuilder::new(1., self.config.tolerance);
+        builder.move_to(0., bottom);
+        for (x, y) in [(length, bottom), (length, top), (0., top)] { builder.line_to(x, y); }
+        builder.close();
+
+        // a bar is a single region, so it takes the glyphs' uniform depth
+        let config = Config{contour_depth: None, ..self.config.clone()};
+        build_mesh(builder, &config, &mut self.tessellator.borrow_mut())
+    }
+
+    /// Places the glyphs of a single line of text from the origin, returning each glyph with its
+    /// position, and the length of the line.
+    fn place_glyphs(&self, line: &str, layout: &TextLayout)
+        -> Result<(Placement, f32)>
+    {
+        let vertical = layout.writing_mode == WritingMode::Vertical;
+        let mut placed = Vec::new();
+        let mut pen = 0.;
+        let mut prev = None;
+        for c in line.chars().filter(|c| !c.is_control()) {
+            let Some(mut glyph) = self.text_glyph(c)? else { continue };
+            if vertical && layout.vertical_alternates {
+                glyph = self.vertical_alternate(glyph);
+            }
+
+            if let Some(left) = prev {
+                pen += layout.letter_spacing;
+                if layout.kerning && !vertical { pen += self.kerning(left, glyph); }
+            }
+            prev = Some(glyph);
+
+            if vertical {
+                let ([x, y], advance) = self.vertical_metrics(glyph);
+                placed.push((c, glyph, [x, y - pen]));
+                pen += advance;
+            }
+            else {
+                placed.push((c, glyph, [pen, 0.]));
+                pen += self.advance(glyph);
+            }
+        }
+        Ok((placed, pen))
+    }
+
+    /// Wraps a paragraph into lines, and reorders each into visual order.
+    fn visual_lines<'t>(&self, paragraph: &'t str, layout: &TextLayout)
+        -> Result<Vec<Cow<'t, str>>>
+    {
+        let bidi = BidiInfo::new(paragraph, None);
+        let lines = self.wrap(paragraph, layout)?.into_iter()
+            .map(|line| {
+                let start = line.as_ptr() as usize - paragraph.as_ptr() as usize;
+                let range = start .. start + line.len();
+                match bidi.paragraphs.iter().find(|para| para.range.contains(&range.start)) {
+                    Some(para) => bidi.reorder_line(para, range),
+                    None => Cow::Borrowed(line),
+                }
+            })
+            .collect();
+        Ok(lines)
+    }
+
+    /// Splits a paragraph into lines no longer than the layout's `max_width`, breaking at
+    /// whitespace, which is dropped at each break.
+    fn wrap<'t>(&self, paragraph: &'t str, layout: &TextLayout) -> Result<Vec<&'t str>> {
+        let Some(max_width) = layout.max_width else { return Ok(vec![paragraph]) };
+
+        let words = paragraph.split_whitespace().map(|word| {
+            let start = word.as_ptr() as usize - paragraph.as_ptr() as usize;
+            start .. start + word.len()
+        });
+
+        let mut lines = Vec::new();
+        let (mut start, mut end) = (0, 0);
+        for word in words {
+            let too_wide = self.place_glyphs(&paragraph[start .. word.end], layout)?.1 > max_width;
+            if end > start && too_wide {
+                lines.push(&paragraph[start .. end]);
+                start = word.start;
+            }
+            end = word.end;
+        }
+        lines.push(&paragraph[start .. end]);
+        Ok(lines)
+    }
+
+    /// Generates a single [Mesh] for some text, as [layout_text](Self::layout_text) does, but
+    /// meshes the distinct glyphs of the text that aren't cached yet in parallel first, each
+    /// thread with its own tessellators and scratch buffers. The result is the same; the glyphs
+    /// are added to the cache, so this pays off most for long text in a fresh generator.
+    ///
+    /// Only available with the `rayon` feature.
+    ///
+    /// Arguments:
+    /// * `text`: The text to be meshed.
+    /// * `layout`: The [TextLayout] that should be used.
+    ///
+    /// Returns:
+    /// A [Result] containing the [TextMesh] if successful, otherwise an [Error].
+    #[cfg(feature = "rayon")]
+    pub fn layout_text_parallel(&self, text: &str, layout: &TextLayout) -> Result<TextMesh> {
+        use {crate::mesh_face_glyph, rayon::prelude::*};
+
+        // the glyphs as placed, so with their vertical alternates in vertical text
+        let alternates = layout.writing_mode == WritingMode::Vertical && layout.vertical_alternates;
+        let mut glyphs: Vec<GlyphId> = text.chars()
+            .filter(|c| !c.is_control())
+            .filter_map(|c| self.text_glyph(c).ok().flatten())
+            .map(|glyph| if alternates {self.vertical_alternate(glyph)} else {glyph})
+            .collect();
+        glyphs.sort_unstable();
+        glyphs.dedup();
+        glyphs.retain(|glyph| !self.cache.borrow().contains_key(glyph));
+
+        // the generator can't be shared between threads, but its face and settings can
+        let (face, scale, config) = (&self.face, self.scale, &self.config);
+        let meshed: Vec<(GlyphId, Result<Mesh>)> = glyphs.into_par_iter()
+            .map_init(crate::Tessellator::default, |tess, glyph| {
+                (glyph, mesh_face_glyph(face, scale, glyph, config, tess))
+            })
+            .collect();
+
+        // failures are left for the layout to report in order
+        self.cache.borrow_mut().extend(meshed.into_iter()
+            .filter_map(|(glyph, mesh)| Some((glyph, mesh.ok()?))));
+        self.layout_text(text, layout)
+    }
+
+    /// Measures some text as [layout_text](Self::layout_text) would lay it out, without meshing
+    /// it, so that UI code can size and wrap text before paying for tessellation.
+    ///
+    /// Only the glyphs' advances and kerning are walked, and their outlines are flattened for
+    /// the bounding box. The box covers the outlines as adjusted by the [Config], through the
+    /// depth of the extrusion; it leaves out the extra reach of strokes and oblique extrusion,
+    /// and of underlines and plates.
+    ///
+    /// Arguments:
+    /// * `text`: The text to be measured.
+    /// * `layout`: The [TextLayout] that should be used.
+    ///
+    /// Returns:
+    /// A [Result] containing the [TextMetrics] if successful, otherwise an [Error].
+    pub fn measure(&self, text: &str, layout: &TextLayout) -> Result<TextMetrics> {
+        let mut outline_boxes = HashMap::new();
+        let mut outline_box = |glyph| *outline_boxes.entry(glyph).or_insert_with(|| {
+            let mut outline = self.outline(glyph, self.config.tolerance)?;
+            outline.adjust(&self.config);
+            outline.bbox
+        });
+
+        let z = self.config.half_depth();
+        let mut metrics = TextMetrics::default();
+        let mut bbox: Option<BoundingBox> = None;
+        for (i, line) in self.wrapped_lines(text, layout)?.iter().enumerate() {
+            let (placed, length) = self.place_glyphs(line, layout)?;
+            let [ox, oy, _] = self.line_origin(layout, i, length);
+            for (_, glyph, [x, y]) in placed {
+                let Some([x0, y0, x1, y1]) = outline_box(glyph) else { continue };
+                let [x, y] = [ox + x, oy + y];
+                let glyph_box = BoundingBox::new([x + x0, y + y0, -z], [x + x1, y + y1, z]);
+                bbox = Some(bbox.map_or(glyph_box, |bbox| bbox.union(&glyph_box)));
+            }
+            metrics.width = metrics.width.max(length);
+            metrics.line_widths.push(length);
+        }
+        metrics.line_count = metrics.line_widths.len();
+        metrics.bbox = bbox.unwrap_or_default().in_coordinates(self.config.coordinates);
+        Ok(metrics)
+    }
+
+    /// Computes the fraction of the area of some text's advance boxes that its glyphs cover,
+    /// such as to estimate how dense a layout is, or to choose a contrast.
+    ///
+    /// Each line's advance box is as long as the line, by the advances of its glyphs, and one
+    /// line height across. The ink is the area of the glyphs' front caps, so glyphs that overlap
+    /// each other are counted twice, and a [Config] without front caps covers
+    /// nothing. Underlines, strikethroughs and plates aren't counted. Text without any advance
+    /// has a coverage of 0.
+    ///
+    /// Arguments:
+    /// * `text`: The text to be measured.
+    /// * `layout`: The [TextLayout] that should be used.
+    ///
+    /// Returns:
+    /// A [Result] containing the coverage if successful, otherwise an [Error].
+    pub fn ink_coverage(&self, text: &str, layout: &TextLayout) -> Result<f32> {
+        let mut areas = HashMap::new();
+        let mut ink = 0.;
+        for (_, glyph, _) in self.layout_glyphs(text, layout)? {
+            ink += match areas.entry(glyph) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => *entry.insert(front_area(&self.cached_glyph(glyph)?)),
+            };
+        }
+
+        let mut length = 0.;
+        for line in self.wrapped_lines(text, layout)? {
+            length += self.place_glyphs(&line, layout)?.1;
+        }
+        let area = length * self.line_pitch(layout);
+        Ok(if area > 0. {ink / area} else {0.})
+    }
+
+    /// Generates the [Mesh] for the given `glyph`, as [generate_mesh](Self::generate_mesh) does,
+    /// along with its horizontal advance and left side bearing from the face's `hmtx` table, for
+    /// callers doing their own layout. These are the same metrics that
+    /// [layout_text](Self::layout_text) places glyphs by, before kerning and letter spacing.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    ///
+    /// Returns:
+    /// A [Result] containing the [GlyphMesh] if successful, otherwise an [Error].
+    pub fn generate_glyph_mesh(&self, glyph: GlyphId) -> Result<GlyphMesh> {
+        let mesh = self.generate_mesh(glyph)?;
+        let bearing = match glyph {
+            TOFU => mesh.bbox.mins[0],
+            _ => self.face.glyph_hor_side_bearing(glyph).unwrap_or(0) as f32 * self.scale,
+        };
+        Ok(GlyphMesh{mesh, advance: self.advance(glyph), bearing, glyph_id: glyph})
+    }
+
+    /// Meshes the glyphs of each character in `chars` ahead of time, so that later calls find
+    /// them in the cache; for example, to tessellate a character set during a loading screen.
+    /// Characters are mapped to glyphs as in [layout_text](Self::layout_text); characters the
+    /// face has no glyph for are failures if the [Config] says to report them.
+    ///
+    /// Arguments:
+    /// * `chars`: The characters to be meshed.
+    ///
+    /// Returns:
+    /// The characters whose glyphs couldn't be meshed, each with its [Error].
+    pub fn precache(&mut self, chars: &str) -> Vec<(char, Error)> {
+        let mut failures: Vec<(char, Error)> = Vec::new();
+        for c in chars.chars().filter(|c| !c.is_control()) {
+            if failures.iter().any(|&(failed, _)| failed == c) { continue; }
+            let cached = self.text_glyph(c)
+                .and_then(|glyph| glyph.map_or(Ok(()), |glyph| self.cached_glyph(glyph).map(drop)));
+            if let Err(error) = cached {
+                failures.push((c, error));
+            }
+        }
+        failures
+    }
+
+    /// The glyph used for `c` in text, or `None` if it is skipped, following the [Config]'s
+    /// [MissingGlyph] policy where the face has no glyph for it.
+    ///
+    /// [Config]: crate::Config
+    fn text_glyph(&self, c: char) -> Result<Option<GlyphId>> {
+        match (self.face.glyph_index(c), self.config.missing_glyph) {
+            (Some(glyph), _) => Ok(Some(glyph)),
+            (None, MissingGlyph::NotdefMesh) => Ok(Some(GlyphId(0))),
+            (None, MissingGlyph::Skip) => Ok(None),
+            (None, MissingGlyph::Error) => Err(Error::MissingGlyph(c)),
+            (None, MissingGlyph::TofuBox) => Ok(Some(TOFU)),
+        }
+    }
+
+    /// The horizontal advance of `glyph`, in mesh units. The placeholder box takes the advance of
+    /// the face's missing glyph.
+    fn advance(&self, glyph: GlyphId) -> f32 {
+        let glyph = if glyph == TOFU {GlyphId(0)} else {glyph};
+        self.face.glyph_hor_advance(glyph).unwrap_or(0) as f32 * self.scale
+    }
+
+    /// The offset from the pen position to the origin of `glyph` in vertical text, and its
+    /// vertical advance, in mesh units.
+    ///
+    /// The glyph is centred horizontally on the pen, and placed with its vertical origin on it:
+    /// from the `VORG` table, or else from the top side bearing and the glyph's bounding box, or
+    /// else at the ascender. The advance comes from the `vmtx` table, or else is the face's height.
+    fn vertical_metrics(&self, glyph: GlyphId) -> ([f32; 2], f32) {
+        let glyph = if glyph == TOFU {GlyphId(0)} else {glyph};
+        let face = &self.face;
+        let y_origin = face.glyph_y_origin(glyph)
+            .or_else(|| {
+                let bearing = face.glyph_ver_side_bearing(glyph)?;
+                Some(bearing.saturating_add(face.glyph_bounding_box(glyph)?.y_max))
+            })
+            .map_or(face.ascender() as f32, f32::from);
+        let advance = face.glyph_ver_advance(glyph).map_or(face.height() as f32, f32::from);
+        let half_width = 0.5 * face.glyph_hor_advance(glyph).unwrap_or(0) as f32;
+        ([-half_width * self.scale, -y_origin * self.scale], advance * self.scale)
+    }
+
+    /// The vertical alternate of `glyph` from the `vert` feature of the face's `GSUB` table, or
+    /// `glyph` itself if it has none.
+    fn vertical_alternate(&self, glyph: GlyphId) -> GlyphId {
+        let Some(gsub) = self.face.tables().gsub else { return glyph };
+        feature_lookups(&gsub, VERT).into_iter()
+            .filter_map(|index| gsub.lookups.get(index))
+            .fold(glyph, |glyph, lookup| {
+                lookup.subtables.into_iter::<SubstitutionSubtable>()
+                    .find_map(|subtable| {
+                        let SubstitutionSubtable::Single(single) = subtable else { return None };
+                        match single {
+                            SingleSubstitution::Format1{coverage, delta} => {
+                                coverage.get(glyph)?;
+                                Some(GlyphId(glyph.0.wrapping_add(delta as u16)))
+                            }
+                            SingleSubstitution::Format2{coverage, substitutes} =>
+                                substitutes.get(coverage.get(glyph)?),
+                        }
+                    })
+                    .unwrap_or(glyph)
+            })
+    }
+
+    /// The adjustment to the advance between `left` and `right`, in mesh units. This comes from
+    /// the pair adjustments of the `kern` feature in the face's `GPOS` table if it has one, as
+    /// text shapers prefer it, and otherwise from the `kern` table.
+    fn kerning(&self, left: GlyphId, right: GlyphId) -> f32 {
+        let tables = self.face.tables();
+        let units = match tables.gpos.filter(|gpos| gpos.features.index(KERN).is_some()) {
+            Some(gpos) => gpos_kerning(&gpos, left, right),
+            None => kern_table_kerning(&self.face, left, right),
+        };
+        units as f32 * self.scale
+    }
+}
+
+/// The area of the front cap of a glyph's `mesh`.
+fn front_area(mesh: &Mesh) -> f32 {
+    mesh.indices[mesh.part(SubmeshKind::Front)].as_chunks::<3>().0.iter()
+        .map(|t| t.map(|i| mesh.vertices[i as usize]))
+        .map(|[a, b, c]| length(cross(sub(b, a), sub(c, a))) / 2.)
+        .sum()
+}
+
+const KERN: Tag = Tag::from_bytes(b"kern");
+const VERT: Tag = Tag::from_bytes(b"vert");
+
+/// The indices of the lookups of every feature tagged `tag` in `table`, in lookup order.
+fn feature_lookups(table: &LayoutTable<'_>, tag: Tag) -> Vec<u16> {
+    let mut lookups: Vec<u16> = table.features.into_iter()
+        .filter(|feature| feature.tag == tag)
+        .flat_map(|feature| feature.lookup_indices)
+        .collect();
+    lookups.sort_unstable();
+    lookups.dedup();
+    lookups
+}
+
+/// Sums the horizontal pair adjustments for `left` and `right` from the lookups of every `kern`
+/// feature in `gpos`, in font units. Within a lookup, only the first matching subtable applies.
+fn gpos_kerning(gpos: &LayoutTable<'_>, left: GlyphId, right: GlyphId) -> i32 {
+    feature_lookups(gpos, KERN).into_iter()
+        .filter_map(|index| gpos.lookups.get(index))
+        .filter_map(|lookup| {
+            lookup.subtables.into_iter::<PositioningSubtable>().find_map(|subtable| {
+                let PositioningSubtable::Pair(pair) = subtable else { return None };
+                let covered = pair.coverage().get(left)?;
+                let (first, _) = match pair {
+                    PairAdjustment::Format1{sets, ..} => sets.get(covered)?.get(right)?,
+                    PairAdjustment::Format2{classes, matrix, ..} =>
+                        matrix.get((classes.0.get(left), classes.1.get(right)))?,
+                };
+                Some(i32::from(first.x_advance))
+            })
+        })
+        .sum()
+}
+
+/// Sums the pair adjustments for `left` and `right` from the plain horizontal subtables of the
+/// `kern` table of `face`, in font units.
+fn kern_table_kerning(face: &ttf_parser::Face<'_>, left: GlyphId, right: GlyphId) -> i32 {
+    let Some(kern) = face.tables().kern else { return 0 };
+    kern.subtables.into_iter()
+        .filter(|st| st.horizontal && !st.variable && !st.has_cross_stream)
+        .filter_map(|st| st.glyphs_kerning(left, right))
+        .map(i32::from)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cross, sub, test_face, test_font::{put16, put32, rect, FontBuilder}, Align, BoundingBox,
+        Config, CoordinateSystem, FaceFlags, Mesh, MeshGenerator, MissingGlyph, Plate,
+        StrokeConfig, StrokeJoin, SubmeshKind, TextLayout, Units, WritingMode, TOFU,
+    };
+
+    #[test]
+    fn baked_glyphs_concatenate_to_text() {
+        let face = test_face();
+        let generator = MeshGenerator::new(&face);
+        let layout = TextLayout{bake_positions: true, ..Default::default()};
+        let text = generator.layout_text("AV To", &layout).unwrap().mesh;
+        let glyphs = generator.layout_text_glyphs("AV To", &layout).unwrap();
+        assert_eq!(glyphs.len(), 5);
+
+        let mut concatenated = Mesh::default();
+        for glyph in &glyphs { concatenated.append_translated(&glyph.mesh, [0.; 3]); }
+        assert_eq!(concatenated, text);
+
+        // without baking, each mesh is at its own origin
+        let layout = TextLayout{bake_positions: false, ..layout};
+        let unbaked = generator.layout_text_glyphs("AV To", &layout).unwrap();
+        for (baked, unbaked) in glyphs.iter().zip(&unbaked) {
+            assert_eq!(baked.position, unbaked.position);
+            assert_eq!(unbaked.mesh, generator.generate_mesh(unbaked.glyph_id).unwrap());
+        }
+    }
+
+    #[test]
+    fn glyph_bounds_cover_text() {
+        let face = test_face();
+        let config = Config{coordinates: CoordinateSystem::ZUpRightHanded, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let layout = TextLayout{glyph_bounds: true, align: Align::Center, ..Default::default()};
+        let text = generator.layout_text("Hi\nyo u", &layout).unwrap();
+
+        let chars: String = text.glyphs.iter().map(|&(c, _)| c).collect();
+        assert_eq!(chars, "Hiyou");
+        let union = text.glyphs.iter().skip(1).fold(text.glyphs[0].1, |u, (_, b)| u.union(b));
+        assert_eq!(union, text.mesh.bbox);
+
+        // the boxes are in mesh coordinates, with the text standing in the XZ plane
+        let [(_, h), (_, i), (_, y), ..] = text.glyphs[..] else { unreachable!() };
+        assert!(h.maxs[0] <= i.mins[0]);
+        assert!(y.maxs[2] < h.mins[2]);
+        let plain = generator.layout_text("Hi\nyo u", &TextLayout::default()).unwrap();
+        assert!(plain.glyphs.is_empty());
+    }
+
+    #[test]
+    fn lines_snap_to_baseline_grid() {
+        let face = test_face();
+        let generator = MeshGenerator::new(&face);
+        let line_height = generator.line_height();
+        let baselines = |baseline_grid, line_height| {
+            let layout = TextLayout{baseline_grid, line_height, ..Default::default()};
+            let text = generator.layout_text("x\nx\nx", &layout).unwrap();
+            text.lines.iter().map(|line| line.mins[1]).collect::<Vec<_>>()
+        };
+
+        assert_eq!(baselines(None, None), [0., -line_height, -2. * line_height]);
+
+        // a line one and a half rows tall takes up two rows
+        let grid = line_height / 1.5;
+        assert_eq!(baselines(Some(grid), None), [0., -2. * grid, -4. * grid]);
+
+        // a line exactly one row tall takes up one row
+        assert_eq!(baselines(Some(0.25), Some(0.25)), [0., -0.25, -0.5]);
+    }
+
+    #[test]
+    fn ink_coverage_of_a_square() {
+        // the box of MissingGlyph::TofuBox is a square ring of known area
+        let face = test_face();
+        let config = Config{missing_glyph: MissingGlyph::TofuBox, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let tofu = generator.generate_glyph_mesh(TOFU).unwrap();
+        let [w, h, _] = tofu.mesh.bbox.size();
+        let stroke = 0.08 * generator.em_size();
+        let ring = w * h - (w - 2. * stroke) * (h - 2. * stroke);
+
+        let layout = TextLayout::default();
+        let expected = 2. * ring / (2. * tofu.advance * generator.line_height());
+        let coverage = generator.ink_coverage("\u{e000}\u{e001}", &layout).unwrap();
+        assert!((coverage - expected).abs() < 1e-4, "{coverage} is not {expected}");
+        assert_eq!(generator.ink_coverage("", &layout).unwrap(), 0.);
+    }
+
+    /// A font with a CJK glyph, its vertical metrics, and a narrower vertical alternate of it.
+    fn vertical_font() -> Vec<u8> {
+        let mut font = FontBuilder::new();
+        let glyph = font.glyph(Some('縦'), 600, vec![rect(100, 0, 500, 700)]);
+        let alternate = font.glyph(None, 600, vec![rect(250, 0, 350, 700)]);
+
+        let mut vhea = Vec::new();
+        put32(&mut vhea, 0x0001_1000);
+        put16(&mut vhea, 500);
+        put16(&mut vhea, (-500i16) as u16);
+        vhea.extend([0; 26]);
+        put16(&mut vhea, 3);
+        font.table(b"vhea", vhea);
+
+        // advance heights and top side bearings
+        let mut vmtx = Vec::new();
+        for (advance, bearing) in [(1000, 0), (900, 100), (900, 100)] {
+            put16(&mut vmtx, advance);
+            put16(&mut vmtx, bearing);
+        }
+        font.table(b"vmtx", vmtx);
+
+        // a vert feature with one single substitution, and no scripts
+        let mut gsub = Vec::new();
+        for v in [1, 0, 10, 12, 26] { put16(&mut gsub, v); }
+        put16(&mut gsub, 0); // script list
+        put16(&mut gsub, 1); // feature list
+        gsub.extend(b"vert");
+        for v in [8, 0, 1, 0] { put16(&mut gsub, v); }
+        put16(&mut gsub, 1); // lookup list
+        for v in [4, 1, 0, 1, 8] { put16(&mut gsub, v); }
+        for v in [1, 6, alternate - glyph] { put16(&mut gsub, v); }
+        for v in [1, 1, glyph] { put16(&mut gsub, v); }
+        font.table(b"GSUB", gsub);
+        font.build()
+    }
+
+    #[test]
+    fn vertical_text_uses_vertical_metrics() {
+        let data = vertical_font();
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+        let config = Config{units: Units::FontUnits, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let glyph_bounds = |vertical_alternates| {
+            let layout = TextLayout {
+                writing_mode: WritingMode::Vertical,
+                vertical_alternates,
+                glyph_bounds: true,
+                ..Default::default()
+            };
+            let text = generator.layout_text("縦縦", &layout).unwrap();
+            text.glyphs.into_iter().map(|(_, bounds)| bounds).collect::<Vec<_>>()
+        };
+
+        // glyphs advance down by their vmtx advance, centred on the column
+        let [first, second] = glyph_bounds(false)[..] else { panic!("expected two glyphs") };
+        assert_eq!(first.maxs[1] - second.maxs[1], 900.);
+        assert_eq!(first.maxs[0] - first.mins[0], 400.);
+        assert_eq!(first.mins[0] + first.maxs[0], second.mins[0] + second.maxs[0]);
+
+        // the vert feature swaps in the narrower alternate, at the same place
+        let [first_alt, _] = glyph_bounds(true)[..] else { panic!("expected two glyphs") };
+        assert_eq!(first_alt.maxs[0] - first_alt.mins[0], 100.);
+        assert_eq!(first_alt.maxs[1], first.maxs[1]);
+        assert_eq!(first_alt.mins[0] + first_alt.maxs[0], first.mins[0] + first.maxs[0]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_layout_meshes_vertical_alternates() {
+        let data = vertical_font();
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+        let generator = MeshGenerator::new(&face);
+        let layout = TextLayout{writing_mode: WritingMode::Vertical, ..Default::default()};
+        let text = generator.layout_text_parallel("縦縦", &layout).unwrap();
+
+        // only the alternate is placed, so only it is meshed
+        assert_eq!(generator.cache_len(), 1);
+        assert_eq!(text.mesh, generator.layout_text("縦縦", &layout).unwrap().mesh);
+        assert_eq!(generator.cache_len(), 1);
+    }
+
+    #[test]
+    fn plate_is_a_submesh() {
+        let face = test_face();
+        let generator = MeshGenerator::new(&face);
+        let plate = Plate{padding: 0.1, corner_radius: 0.05, depth: Some(0.2)};
+        let layout = TextLayout{plate: Some(plate), ..Default::default()};
+        let mut mesh = generator.layout_text("Hi", &layout).unwrap().mesh;
+        let text = generator.layout_text("Hi", &TextLayout::default()).unwrap().mesh;
+
+        // the plate's triangles follow the text's, in a single submesh
+        let (kind, range) = mesh.submeshes.last().cloned().unwrap();
+        assert_eq!(kind, SubmeshKind::Plate);
+        assert_eq!(mesh.submeshes[.. mesh.submeshes.len() - 1], text.submeshes);
+        assert_eq!(range.start as usize, text.indices.len());
+        assert_eq!(range.end as usize, mesh.indices.len());
+        let rear = text.bbox.mins[2];
+        for &i in &mesh.indices[range.start as usize ..] {
+            assert!(mesh.vertices[i as usize][2] <= rear + 1e-6);
+        }
+
+        let mut obj = Vec::new();
+        mesh.write_obj(&mut obj, true).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+        assert!(obj.contains("g plate") && !obj.contains("g other"));
+
+        let mut bin = Vec::new();
+        mesh.write_bin(&mut bin).unwrap();
+        assert_eq!(Mesh::read_bin(&bin[..]).unwrap(), mesh);
+
+        let submeshes = mesh.submeshes.clone();
+        mesh.optimize_cache();
+        assert_eq!(mesh.submeshes, submeshes);
+        for &i in &mesh.indices[range.start as usize ..] {
+            assert!(mesh.vertices[i as usize][2] <= rear + 1e-6);
+        }
+    }
+
+    #[test]
+    fn plate_ignores_glyph_shaping() {
+        let face = test_face();
+        let config = Config {
+            tolerance: 1e-4,
+            stroke: Some(StrokeConfig{width: 0.02, join: StrokeJoin::Miter}),
+            taper: 0.5,
+            twist_degrees: 45.,
+            faces: FaceFlags{back: false, ..Default::default()},
+            ..Default::default()
+        };
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let corner_radius = 0.05;
+        let plate = Plate{padding: 0.1, corner_radius, depth: Some(0.2)};
+        let layout = TextLayout{plate: Some(plate), ..Default::default()};
+        let mesh = generator.layout_text("Hi", &layout).unwrap().mesh;
+        let (_, range) = mesh.submeshes.last().cloned().unwrap();
+
+        // a solid cap facing each way, each covering the whole rounded rectangle in one plane
+        let mut caps = [(0., vec![]), (0., vec![])];
+        for t in mesh.indices[range.start as usize .. range.end as usize].as_chunks::<3>().0 {
+            let [a, b, c] = t.map(|i| mesh.vertices[i as usize]);
+            let normal = cross(sub(b, a), sub(c, a));
+            if normal[2].abs() < 1e-9 { continue; }
+            let (area, points) = &mut caps[(normal[2] < 0.) as usize];
+            *area += normal[2].abs() / 2.;
+            points.extend([a, b, c]);
+        }
+        let [front, back] = caps.map(|(area, points)| (area, BoundingBox::from_points(&points)));
+        let [w, h, _] = front.1.size();
+        let expected = w * h - (4. - std::f32::consts::PI) * corner_radius * corner_radius;
+        for (area, bbox) in [front, back] {
+            assert!((area - expected).abs() < 1e-3 * expected, "{area} is not {expected}");
+            assert_eq!(bbox.size()[2], 0.);
+            assert_eq!(bbox.size()[.. 2], [w, h]);
+        }
+        assert!((front.1.mins[2] - back.1.mins[2] - 0.2).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,190 @@
+//! Selecting a location in the design space of variable fonts.
+
+use crate::{face_name, mesh_scale, Error, MeshGenerator, Result, Tag};
+
+/// A named instance of a variable font, such as "Bold Condensed": a named location in its design
+/// space.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedInstance {
+    /// The instance's subfamily name, if the face's `name` table has a readable one.
+    pub name: Option<String>,
+    /// The value of each of the face's axes at this instance, in design units.
+    pub coordinates: Vec<(Tag, f32)>,
+}
+
+impl<'face> MeshGenerator<'face> {
+    /// Sets the location in the face's design space at which glyphs are outlined, for variable
+    /// fonts. Axes that aren't given keep their current values, which start at their defaults.
+    ///
+    /// This empties the glyph mesh cache, and updates the face's metrics, including the scale of
+    /// generated meshes.
+    ///
+    /// Arguments:
+    /// * `axes`: The axes to set, such as `wght`, `wdth` or `slnt`, each with its value in design
+    ///   units. Values are clamped to the axis' range.
+    ///
+    /// Returns:
+    /// An [Error] if the face has no such axis, in which case no axes are changed.
+    pub fn set_variation(&mut self, axes: &[(Tag, f32)]) -> Result<()> {
+        let known = |tag| self.face.variation_axes().into_iter().any(|axis| axis.tag == tag);
+        if let Some(&(tag, _)) = axes.iter().find(|&&(tag, _)| !known(tag)) {
+            return Err(Error::UnknownVariationAxis(tag));
+        }
+
+        for &(tag, value) in axes { self.face.set_variation(tag, value); }
+        self.scale = mesh_scale(&self.face, &self.config);
+        self.clear_cache();
+        Ok(())
+    }
+
+    /// Get the named instances of the face, from its `fvar` table. Faces that aren't variable have
+    /// none.
+    pub fn named_instances(&self) -> Vec<NamedInstance> {
+        let Some(data) = self.face.raw_face().table(Tag::from_bytes(b"fvar")) else {
+            return Vec::new()
+        };
+        let tags: Vec<Tag> = self.face.variation_axes().into_iter().map(|axis| axis.tag).collect();
+        parse_instances(data, &tags)
+            .into_iter()
+            .map(|(name_id, values)| NamedInstance {
+                name: face_name(&self.face, name_id),
+                coordinates: tags.iter().copied().zip(values).collect(),
+            })
+            .collect()
+    }
+
+    /// Moves to one of the face's [named instances](Self::named_instances), as with
+    /// [set_variation](Self::set_variation).
+    ///
+    /// Arguments:
+    /// * `index`: The index of the instance in the list of named instances.
+    ///
+    /// Returns:
+    /// An [Error] if the face has no such instance.
+    pub fn set_named_instance(&mut self, index: usize) -> Result<()> {
+        let instance = self.named_instances().into_iter().nth(index)
+            .ok_or(Error::UnknownNamedInstance)?;
+        self.set_variation(&instance.coordinates)
+    }
+
+    /// Moves to the named instance of the face with the given subfamily name, as with
+    /// [set_variation](Self::set_variation).
+    ///
+    /// Arguments:
+    /// * `name`: The name of the instance, such as "Bold Condensed".
+    ///
+    /// Returns:
+    /// An [Error] if the face has no such instance.
+    pub fn set_named_instance_by_name(&mut self, name: &str) -> Result<()> {
+        let instance = self.named_instances().into_iter()
+            .find(|instance| instance.name.as_deref() == Some(name))
+            .ok_or(Error::UnknownNamedInstance)?;
+        self.set_variation(&instance.coordinates)
+    }
+}
+
+/// Reads the instance records of an `fvar` table whose axes are `tags`, as the name ID of each
+/// instance's subfamily name and its coordinates.
+fn parse_instances(data: &[u8], tags: &[Tag]) -> Vec<(u16, Vec<f32>)> {
+    let u16_at = |offset: usize| {
+        data.get(offset .. offset + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    };
+    let fixed_at = |offset: usize| {
+        data.get(offset .. offset + 4)
+            .map(|b| i32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f32 / 65536.)
+    };
+
+    let (Some(axes_offset), Some(axis_count), Some(axis_size), Some(count), Some(size))
+        = (u16_at(4), u16_at(8), u16_at(10), u16_at(12), u16_at(14))
+        else { return Vec::new() };
+    if axis_count as usize != tags.len() || (size as usize) < 4 + 4 * tags.len() {
+        return Vec::new();
+    }
+
+    let start = axes_offset as usize + axis_count as usize * axis_size as usize;
+    (0 .. count as usize)
+        .map_while(|i| {
+            let record = start + i * size as usize;
+            let name_id = u16_at(record)?;
+            let values = (0 .. tags.len())
+                .map(|axis| fixed_at(record + 4 + 4 * axis))
+                .collect::<Option<Vec<f32>>>()?;
+            Some((name_id, values))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_font::{put16, put32, rect, FontBuilder}, Config, MeshGenerator, Tag, Units,
+    };
+
+    const WGHT: Tag = Tag::from_bytes(b"wght");
+
+    /// A font with a weight axis from 100 to 900, along which its one glyph, a 400 unit wide
+    /// rectangle, widens to 600 units at the heaviest, named instance.
+    fn variable_font() -> Vec<u8> {
+        let mut font = FontBuilder::new();
+        font.glyph(Some('I'), 600, vec![rect(100, 0, 500, 700)]);
+
+        let mut fvar = Vec::new();
+        for v in [1, 0, 16, 2, 1, 20, 1, 8] { put16(&mut fvar, v); }
+        fvar.extend(b"wght");
+        for v in [100, 400, 900] { put32(&mut fvar, v << 16); }
+        put16(&mut fvar, 0);
+        put16(&mut fvar, 256);
+        put16(&mut fvar, 257);
+        put16(&mut fvar, 0);
+        put32(&mut fvar, 900 << 16);
+        font.table(b"fvar", fvar);
+
+        // one tuple peaking at the heaviest weight, with deltas for all eight points of the
+        // glyph, including its four phantom points
+        let mut data = Vec::new();
+        for v in [1, 10, 18, 0x8000, 0x4000] { put16(&mut data, v); }
+        data.push(0x40 | 7);
+        for dx in [0, 0, 200, 200, 0, 0, 0, 0] { put16(&mut data, dx); }
+        data.push(0x80 | 7);
+
+        let mut gvar = Vec::new();
+        for v in [1, 0, 1, 0] { put16(&mut gvar, v); }
+        put32(&mut gvar, 0);
+        for v in [2, 1] { put16(&mut gvar, v); }
+        put32(&mut gvar, 32);
+        for offset in [0, 0, data.len() as u32] { put32(&mut gvar, offset); }
+        gvar.extend(data);
+        font.table(b"gvar", gvar);
+        font.build()
+    }
+
+    #[test]
+    fn variation_moves_outlines() {
+        let data = variable_font();
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+        let config = Config{units: Units::FontUnits, extrude: None, ..Default::default()};
+        let mut generator = MeshGenerator::new_with_config(&face, config);
+        let glyph = face.glyph_index('I').unwrap();
+        let width = |generator: &MeshGenerator| {
+            generator.generate_mesh(glyph).unwrap().bbox.size()[0]
+        };
+        assert_eq!(width(&generator), 400.);
+
+        generator.set_variation(&[(WGHT, 900.)]).unwrap();
+        assert_eq!(width(&generator), 600.);
+        generator.set_variation(&[(WGHT, 650.)]).unwrap();
+        assert_eq!(width(&generator), 500.);
+
+        let wdth = Tag::from_bytes(b"wdth");
+        assert!(generator.set_variation(&[(WGHT, 400.), (wdth, 100.)]).is_err());
+        assert_eq!(width(&generator), 500.);
+
+        let instances = generator.named_instances();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].coordinates, [(WGHT, 900.)]);
+        generator.set_variation(&[(WGHT, 400.)]).unwrap();
+        generator.set_named_instance(0).unwrap();
+        assert_eq!(width(&generator), 600.);
+        assert!(generator.set_named_instance(1).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+//! Interleaved and custom vertex buffers.
+
+use crate::{CoordinateSystem, GlyphId, Mesh, MeshGenerator, Result, SubmeshKind};
+
+/// A vertex with all of its attributes, laid out for direct upload to a GPU vertex buffer.
+///
+/// This is `#[repr(C)]`, with no padding: the position is at offset 0, the normal at 12 and the
+/// UV at 24, in a stride of 32 bytes. With the `bytemuck` feature, it implements
+/// `bytemuck::Pod`, so a slice of vertices can be cast to bytes with `bytemuck::cast_slice`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Vertex {
+    /// The position of the vertex.
+    pub position: [f32; 3],
+    /// The normal of the vertex, or zero if the mesh has no normals.
+    pub normal: [f32; 3],
+    /// The texture coordinates of the vertex, or zero if the mesh has no UVs.
+    pub uv: [f32; 2],
+}
+
+// SAFETY: `Vertex` is `repr(C)`, made only of `f32`s, and has no padding.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Vertex { }
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Vertex { }
+
+impl Mesh {
+    /// Interleaves this mesh's positions, normals and texture coordinates into a single vertex
+    /// buffer, to be drawn with [indices](Mesh::indices) as before.
+    pub fn to_interleaved(&self) -> Vec<Vertex> {
+        self.vertices.iter().enumerate()
+            .map(|(i, &position)| Vertex {
+                position,
+                normal: self.normals.get(i).copied().unwrap_or_default(),
+                uv: self.uvs.get(i).copied().unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// Everything known about a vertex of a glyph mesh, as passed to a [VertexCtor].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VertexAttributes {
+    /// The position of the vertex.
+    pub position: [f32; 3],
+    /// The normal of the vertex, if normals are generated.
+    pub normal: Option<[f32; 3]>,
+    /// The texture coordinates of the vertex, if UVs are generated.
+    pub uv: Option<[f32; 2]>,
+    /// The part of the mesh that the vertex belongs to. A vertex shared between parts, as
+    /// happens when normals aren't generated, belongs to the first part that uses it, in the
+    /// order of the mesh's [submeshes](Mesh::submeshes).
+    pub kind: Option<SubmeshKind>,
+    /// The glyph that the vertex belongs to.
+    pub glyph: GlyphId,
+}
+
+/// Builds a caller's own vertex type from the attributes of each vertex, as with lyon's
+/// `BuffersBuilder`. This is implemented for closures taking [VertexAttributes].
+pub trait VertexCtor<V> {
+    /// Builds the vertex with the given `attributes`.
+    fn new_vertex(&mut self, attributes: VertexAttributes) -> V;
+}
+
+impl<V, F: FnMut(VertexAttributes) -> V> VertexCtor<V> for F {
+    fn new_vertex(&mut self, attributes: VertexAttributes) -> V {
+        self(attributes)
+    }
+}
+
+/// Vertices of a caller's own type, with the indices of their triangles, as generated by
+/// [MeshGenerator::generate_vertices].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VertexBuffers<V> {
+    /// The vertices, in the order of the glyph's [Mesh].
+    pub vertices: Vec<V>,
+    /// The indices of the triangles, three per triangle.
+    pub indices: Vec<u32>,
+}
+
+impl<'face> MeshGenerator<'face> {
+    /// Generates the vertices of the given `glyph` in a caller's own vertex format, so that
+    /// engine-specific vertex buffers come straight out of the generator, rather than from a
+    /// conversion pass over a [Mesh] on the caller's side.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    /// * `ctor`: The [VertexCtor] that builds each vertex from its attributes.
+    ///
+    /// Returns:
+    /// A [Result] containing the [VertexBuffers] if successful, otherwise an
+    /// [Error](crate::Error).
+    pub fn generate_vertices<V>(&self, glyph: GlyphId, ctor: &mut impl VertexCtor<V>)
+        -> Result<VertexBuffers<V>>
+    {
+        let mesh = self.generate_mesh(glyph)?;
+
+        let mut kinds = vec![None; mesh.vertices.len()];
+        for (kind, range) in &mesh.submeshes {
+            for &i in &mesh.indices[range.start as usize .. range.end as usize] {
+                kinds[i as usize].get_or_insert(*kind);
+            }
+        }
+
+        let vertices = mesh.vertices.iter().zip(kinds).enumerate()
+            .map(|(i, (&position, kind))| ctor.new_vertex(VertexAttributes {
+                position,
+                normal: mesh.normals.get(i).copied(),
+                uv: mesh.uvs.get(i).copied(),
+                kind,
+                glyph,
+            }))
+            .collect();
+        Ok(VertexBuffers{vertices, indices: mesh.indices})
+    }
+}
+
+/// Receives a glyph mesh piece by piece, as generated by [MeshGenerator::generate_mesh_visit],
+/// such as to write it straight into mapped GPU memory.
+pub trait MeshSink {
+    /// Called once before anything else, with the number of vertices and indices that follow,
+    /// to reserve space for them. Does nothing by default.
+    #[allow(unused_variables)]
+    fn begin(&mut self, vertices: usize, indices: usize) { }
+
+    /// Receives the next vertex, with its normal and texture coordinates if they are generated.
+    /// Vertices are numbered from zero in the order they are received.
+    fn vertex(&mut self, position: [f32; 3], normal: Option<[f32; 3]>, uv: Option<[f32; 2]>);
+
+    /// Receives the vertex numbers of the next triangle. All vertices are received before any
+    /// triangle.
+    fn triangle(&mut self, indices: [u32; 3]);
+}
+
+impl<'face> MeshGenerator<'face> {
+    /// Passes the mesh of the given `glyph` to `sink`, as [generate_mesh](Self::generate_mesh)
+    /// would generate it, without building a [Mesh] of its own to return.
+    ///
+    /// Arguments:
+    /// * `glyph`: The glyph to be meshed.
+    /// * `sink`: The [MeshSink] that receives the mesh.
+    ///
+    /// The sink may use the generator itself while it receives the mesh, such as to mesh
+    /// other glyphs.
+    ///
+    /// Returns:
+    /// An empty [Result] if successful, otherwise an [Error](crate::Error), in which case
+    /// nothing is passed to `sink`.
+    pub fn generate_mesh_visit(&self, glyph: GlyphId, sink: &mut impl MeshSink) -> Result<()> {
+        self.cache_glyph(glyph)?;
+        // the mesh is taken out of the cache while the sink has it, so that the cache isn't
+        // borrowed if the sink calls back into the generator
+        let mesh = self.cache.borrow_mut().remove(&glyph).unwrap();
+        let system = self.config.coordinates;
+
+        sink.begin(mesh.vertices.len(), mesh.indices.len());
+        for (i, &position) in mesh.vertices.iter().enumerate() {
+            let normal = mesh.normals.get(i).map(|&n| system.map(n));
+            sink.vertex(system.map(position), normal, mesh.uvs.get(i).copied());
+        }
+        for &[a, b, c] in mesh.indices.as_chunks().0 {
+            sink.triangle(match system {
+                CoordinateSystem::YUpLeftHanded => [a, c, b],
+                _ => [a, b, c],
+            });
+        }
+        self.cache.borrow_mut().entry(glyph).or_insert(mesh);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_face, GlyphId, Mesh, MeshGenerator, MeshSink};
+
+    /// A sink that meshes another glyph with the generator it is fed by, as it is fed.
+    struct Reentrant<'a, 'face> {
+        generator: &'a MeshGenerator<'face>,
+        other: GlyphId,
+        mesh: Mesh,
+        other_mesh: Option<Mesh>,
+    }
+
+    impl MeshSink for Reentrant<'_, '_> {
+        fn begin(&mut self, _: usize, _: usize) {
+            self.other_mesh = Some(self.generator.generate_mesh(self.other).unwrap());
+        }
+
+        fn vertex(&mut self, position: [f32; 3], _: Option<[f32; 3]>, _: Option<[f32; 2]>) {
+            self.mesh.vertices.push(position);
+        }
+
+        fn triangle(&mut self, indices: [u32; 3]) {
+            self.mesh.indices.extend(indices);
+        }
+    }
+
+    #[test]
+    fn sinks_can_use_the_generator() {
+        let face = test_face();
+        let generator = MeshGenerator::new(&face);
+        let [glyph, other] = ['g', 'h'].map(|c| face.glyph_index(c).unwrap());
+        let mut sink = Reentrant {
+            generator: &generator,
+            other,
+            mesh: Mesh::default(),
+            other_mesh: None,
+        };
+        generator.generate_mesh_visit(glyph, &mut sink).unwrap();
+
+        let mesh = generator.generate_mesh(glyph).unwrap();
+        assert_eq!((&sink.mesh.vertices, &sink.mesh.indices), (&mesh.vertices, &mesh.indices));
+        assert_eq!(sink.other_mesh.unwrap(), generator.generate_mesh(other).unwrap());
+        assert_eq!(generator.cache_len(), 2);
+    }
+}
@@ -0,0 +1,193 @@
+//! Index reordering for post-transform vertex cache locality, after Tom Forsyth's "Linear-Speed
+//! Vertex Cache Optimisation", and vertex reordering for fetch locality.
+
+use crate::Mesh;
+
+const CACHE_SIZE: usize = 32;
+
+fn vertex_score(cache_pos: Option<usize>, valence: u32) -> f32 {
+    if valence == 0 { return -1.; }
+
+    let cache = match cache_pos {
+        None => 0.,
+        // the most recent triangle's vertices are scored flat, so its neighbours don't win
+        // purely by being in the newest slots
+        Some(p) if p < 3 => 0.75,
+        Some(p) => (1. - (p - 3) as f32 / (CACHE_SIZE - 3) as f32).powf(1.5),
+    };
+
+    // favour vertices with few triangles left, to finish them off and free their cache slots
+    cache + 2. * (valence as f32).powf(-0.5)
+}
+
+impl Mesh {
+    /// Reorders the triangles of this mesh to improve GPU vertex cache hit rates.
+    ///
+    /// The set of triangles, and the winding of each, is unchanged; only the order in which they
+    /// appear in the index buffer is. This is worth doing once for static meshes with many
+    /// triangles, such as extruded paragraphs. Triangles are only reordered within each of the
+    /// mesh's [submeshes](Mesh::submeshes), so their ranges stay valid.
+    pub fn optimize_cache(&mut self) {
+        let mut ranges: Vec<_> = self.submeshes.iter()
+            .map(|(_, range)| range.start as usize .. range.end as usize)
+            .collect();
+        if ranges.is_empty() { ranges.push(0 .. self.indices.len()); }
+        for range in ranges {
+            // each part only refers to a few vertices, so work relative to the lowest of them
+            let part = &mut self.indices[range];
+            let (Some(&lo), Some(&hi)) = (part.iter().min(), part.iter().max()) else { continue };
+            let local: Vec<u32> = part.iter().map(|i| i - lo).collect();
+            let ordered = cache_order(&local, (hi - lo + 1) as usize);
+            for (i, local) in part.iter_mut().zip(ordered) { *i = local + lo; }
+        }
+    }
+
+    /// Reorders the vertices of this mesh into the order that its triangles first use them, so
+    /// that the GPU fetches vertex data sequentially rather than jumping around memory.
+    ///
+    /// The indices are remapped to match, so the mesh is otherwise unchanged. Run this after
+    /// [Mesh::optimize_cache], which decides the order of the triangles. Vertices that no
+    /// triangle uses are dropped.
+    pub fn optimize_vertex_fetch(&mut self) {
+        let mut remap = vec![u32::MAX; self.vertices.len()];
+        let mut order = Vec::with_capacity(self.vertices.len());
+        for i in &mut self.indices {
+            let new = &mut remap[*i as usize];
+            if *new == u32::MAX {
+                *new = order.len() as u32;
+                order.push(*i as usize);
+            }
+            *i = *new;
+        }
+
+        self.vertices = order.iter().map(|&i| self.vertices[i]).collect();
+        if !self.normals.is_empty() {
+            self.normals = order.iter().map(|&i| self.normals[i]).collect();
+        }
+        if !self.uvs.is_empty() {
+            self.uvs = order.iter().map(|&i| self.uvs[i]).collect();
+        }
+    }
+}
+
+/// Reorders the triangles in `indices`, which refer to `n_verts` vertices, for cache locality.
+fn cache_order(indices: &[u32], n_verts: usize) -> Vec<u32> {
+    let tris = indices.as_chunks::<3>().0.to_vec();
+
+    // vertex-to-triangle adjacency, with the live triangles of each vertex kept at the front
+    let mut valence = vec![0u32; n_verts];
+    for &i in tris.as_flattened() { valence[i as usize] += 1; }
+
+    let mut offsets = Vec::with_capacity(n_verts + 1);
+    offsets.push(0);
+    for &n in &valence { offsets.push(offsets.last().unwrap() + n as usize); }
+
+    let mut adjacency = vec![0u32; tris.len() * 3];
+    let mut fill = offsets.clone();
+    for (t, tri) in tris.iter().enumerate() {
+        for &i in tri {
+            adjacency[fill[i as usize]] = t as u32;
+            fill[i as usize] += 1;
+        }
+    }
+
+    let mut cache_pos = vec![None; n_verts];
+    let mut scores: Vec<f32> = valence.iter().map(|&n| vertex_score(None, n)).collect();
+    let tri_score = |scores: &[f32], tri: &[u32; 3]| -> f32 {
+        tri.iter().map(|&i| scores[i as usize]).sum()
+    };
+    let mut tri_scores: Vec<f32> = tris.iter().map(|t| tri_score(&scores, t)).collect();
+
+    let mut emitted = vec![false; tris.len()];
+    let mut cache: Vec<u32> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut indices = Vec::with_capacity(indices.len());
+    let mut next_unemitted = 0;
+
+    let mut best = (!tris.is_empty()).then_some(0);
+    while let Some(t) = best {
+        let tri = tris[t];
+        indices.extend_from_slice(&tri);
+        emitted[t] = true;
+
+        for &i in &tri {
+            let i = i as usize;
+            let live = &mut adjacency[offsets[i] .. offsets[i] + valence[i] as usize];
+            let pos = live.iter().position(|&u| u as usize == t).unwrap();
+            live.swap(pos, live.len() - 1);
+            valence[i] -= 1;
+        }
+
+        // move the triangle's vertices to the front of the cache
+        let old_cache = std::mem::take(&mut cache);
+        for &i in tri.iter().chain(&old_cache) {
+            if !cache.contains(&i) { cache.push(i); }
+        }
+        let evicted = cache.split_off(cache.len().min(CACHE_SIZE));
+
+        best = None;
+        let mut best_score = f32::NEG_INFINITY;
+        for &i in cache.iter().chain(&evicted) {
+            let i = i as usize;
+            cache_pos[i] = cache.iter().position(|&c| c as usize == i);
+            scores[i] = vertex_score(cache_pos[i], valence[i]);
+        }
+        for &i in cache.iter().chain(&evicted) {
+            let i = i as usize;
+            for &u in &adjacency[offsets[i] .. offsets[i] + valence[i] as usize] {
+                let u = u as usize;
+                tri_scores[u] = tri_score(&scores, &tris[u]);
+                if tri_scores[u] > best_score {
+                    (best, best_score) = (Some(u), tri_scores[u]);
+                }
+            }
+        }
+
+        if best.is_none() {
+            while next_unemitted < tris.len() && emitted[next_unemitted] {
+                next_unemitted += 1;
+            }
+            best = (next_unemitted < tris.len()).then_some(next_unemitted);
+        }
+    }
+
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_face, Config, Mesh, MeshGenerator};
+
+    /// The triangles in each submesh of `mesh`, each rotated to start at its least index so that
+    /// the winding is kept, in sorted order.
+    fn triangles(mesh: &Mesh) -> Vec<Vec<[u32; 3]>> {
+        mesh.submeshes.iter()
+            .map(|(_, range)| {
+                let part = &mesh.indices[range.start as usize .. range.end as usize];
+                let mut triangles: Vec<[u32; 3]> = part.as_chunks::<3>().0.iter()
+                    .map(|&[a, b, c]| match a.min(b).min(c) {
+                        least if least == a => [a, b, c],
+                        least if least == b => [b, c, a],
+                        _ => [c, a, b],
+                    })
+                    .collect();
+                triangles.sort_unstable();
+                triangles
+            })
+            .collect()
+    }
+
+    #[test]
+    fn optimize_cache_keeps_triangles() {
+        let face = test_face();
+        let config = Config{generate_normals: true, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        let mut mesh = generator.generate_text("BOAg &xy").unwrap();
+        let before = triangles(&mesh);
+        let indices = mesh.indices.clone();
+
+        mesh.optimize_cache();
+        assert_ne!(mesh.indices, indices);
+        assert_eq!(triangles(&mesh), before);
+        assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.vertices.len()));
+    }
+}
@@ -0,0 +1,143 @@
+//! Merging coincident vertices.
+
+use {crate::Mesh, std::collections::HashMap};
+
+impl Mesh {
+    /// Merges vertices that lie within `epsilon` of each other along every axis, and removes the
+    /// triangles that collapse as a result.
+    ///
+    /// Meshes with normals or texture coordinates keep coincident copies of vertices where the
+    /// caps meet the side walls, and between adjacent side walls, so that each copy can carry its
+    /// own attributes; glyphs that touch, as in connected scripts, also meet without sharing
+    /// vertices. Welding joins these into a closed, watertight surface, as 3D printing and
+    /// physics engines expect. A merged vertex can't carry the attributes of all its copies, so
+    /// the mesh's `normals` and `uvs` are cleared. The [submeshes](Mesh::submeshes) are kept in
+    /// step with the indices.
+    ///
+    /// Arguments:
+    /// * `epsilon`: The largest distance between vertices that are merged. Zero merges only
+    ///   exactly equal vertices.
+    pub fn weld(&mut self, epsilon: f32) {
+        // vertices are bucketed into cells `epsilon` across, so matches are in neighbouring cells
+        let cell = |p: [f32; 3]| -> [i64; 3] {
+            if epsilon > 0. {p.map(|x| (x / epsilon).floor() as i64)}
+            else {p.map(|x| (x + 0.).to_bits() as i64)}
+        };
+        let reach = if epsilon > 0. {1} else {0};
+        let near = |a: [f32; 3], b: [f32; 3]| (0 .. 3).all(|k| (a[k] - b[k]).abs() <= epsilon);
+
+        let mut cells: HashMap<[i64; 3], Vec<u32>> = HashMap::new();
+        let mut remap = Vec::with_capacity(self.vertices.len());
+        let mut kept = Vec::new();
+        for (i, &p) in self.vertices.iter().enumerate() {
+            let [x, y, z] = cell(p);
+            let found = (-reach ..= reach)
+                .flat_map(|dx| (-reach ..= reach).map(move |dy| (dx, dy)))
+                .flat_map(|(dx, dy)| (-reach ..= reach).map(move |dz| [x + dx, y + dy, z + dz]))
+                .find_map(|key| {
+                    cells.get(&key)?.iter().copied().find(|&j| near(p, self.vertices[j as usize]))
+                });
+
+            remap.push(match found {
+                Some(j) => remap[j as usize],
+                None => {
+                    cells.entry([x, y, z]).or_default().push(i as u32);
+                    kept.push(i);
+                    kept.len() as u32 - 1
+                }
+            });
+        }
+
+        self.vertices = kept.iter().map(|&i| self.vertices[i]).collect();
+        self.normals.clear();
+        self.uvs.clear();
+
+        // drop collapsed triangles, counting the indices kept before each triangle so that the
+        // submesh ranges can follow
+        let mut indices = Vec::with_capacity(self.indices.len());
+        let mut kept_before = Vec::with_capacity(self.indices.len() / 3 + 1);
+        for tri in self.indices.as_chunks::<3>().0 {
+            kept_before.push(indices.len() as u32);
+            let [a, b, c] = tri.map(|i| remap[i as usize]);
+            if a != b && b != c && c != a { indices.extend([a, b, c]); }
+        }
+        kept_before.push(indices.len() as u32);
+        for (_, range) in &mut self.submeshes {
+            *range = kept_before[range.start as usize / 3] .. kept_before[range.end as usize / 3];
+        }
+        self.indices = indices;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_face, BoundingBox, Config, Mesh, MeshGenerator, SubmeshKind};
+
+    /// Asserts that the submesh ranges of `mesh` follow each other from the first index to the
+    /// last, and hold whole triangles.
+    fn assert_partitions(mesh: &Mesh) {
+        let mut end = 0;
+        for (_, range) in &mesh.submeshes {
+            assert_eq!(range.start, end);
+            assert!(range.end >= range.start && range.end % 3 == 0);
+            end = range.end;
+        }
+        assert_eq!(end as usize, mesh.indices.len());
+    }
+
+    #[test]
+    fn weld_closes_glyphs() {
+        let face = test_face();
+        let config = Config{tolerance: 0.005, generate_normals: true, ..Default::default()};
+        let generator = MeshGenerator::new_with_config(&face, config);
+        for c in ['B', 'O'] {
+            let mut mesh = generator.generate_mesh(face.glyph_index(c).unwrap()).unwrap();
+            let (vertices, triangles) = (mesh.vertices.len(), mesh.indices.len() / 3);
+            mesh.weld(0.);
+            assert!(mesh.vertices.len() < vertices);
+            assert_eq!(mesh.indices.len() / 3, triangles);
+            assert!(mesh.normals.is_empty() && mesh.uvs.is_empty());
+            assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.vertices.len()));
+            assert_partitions(&mesh);
+
+            // each edge runs once either way, between two triangles wound alike
+            let mut edges = std::collections::HashMap::new();
+            for &[a, b, c] in mesh.indices.as_chunks::<3>().0 {
+                for edge in [(a, b), (b, c), (c, a)] { *edges.entry(edge).or_insert(0) += 1; }
+            }
+            for (&(a, b), &n) in &edges {
+                assert_eq!((n, edges.get(&(b, a))), (1, Some(&1)), "{c}: edge {a}-{b}");
+            }
+        }
+    }
+
+    #[test]
+    fn weld_merges_across_cells() {
+        // the middle triangle's first two corners are within 0.1 but in different cells, so it
+        // collapses, leaving its submesh empty
+        let vertices = vec![
+            [0., 0., 0.], [1., 0., 0.], [0., 1., 0.],
+            [0.0999, 2., 0.], [0.1001, 2., 0.], [0.1, 3., 0.],
+            [0.1002, 2., 0.], [1., 2., 0.], [1., 3., 0.],
+        ];
+        let mut mesh = Mesh {
+            bbox: BoundingBox::from_points(&vertices),
+            indices: (0 .. 9).collect(),
+            vertices,
+            submeshes: vec![
+                (SubmeshKind::Front, 0 .. 3),
+                (SubmeshKind::Sides, 3 .. 6),
+                (SubmeshKind::Back, 6 .. 9),
+            ],
+            ..Default::default()
+        };
+        mesh.weld(0.1);
+
+        assert_eq!(mesh.vertices.len(), 7);
+        assert_eq!(mesh.indices, [0, 1, 2, 3, 5, 6]);
+        assert_eq!(mesh.vertices[3], [0.0999, 2., 0.]);
+        let ranges: Vec<_> = mesh.submeshes.iter().map(|(_, range)| range.clone()).collect();
+        assert_eq!(ranges, [0 .. 3, 3 .. 3, 3 .. 6]);
+        assert_partitions(&mesh);
+    }
+}
@@ -0,0 +1,46 @@
+//! Uploading meshes to `wgpu` buffers.
+
+use {
+    crate::{Mesh, Vertex},
+    wgpu::{util::DeviceExt, Buffer, BufferUsages, Device, VertexAttribute, VertexBufferLayout},
+};
+
+const ATTRIBUTES: [VertexAttribute; 3] = wgpu::vertex_attr_array![
+    0 => Float32x3,
+    1 => Float32x3,
+    2 => Float32x2,
+];
+
+impl Vertex {
+    /// The layout of a vertex buffer of [Vertex]es, as created by [Mesh::create_buffers], with
+    /// the position, normal and UV at shader locations 0, 1 and 2.
+    pub const LAYOUT: VertexBufferLayout<'static> = VertexBufferLayout {
+        array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &ATTRIBUTES,
+    };
+}
+
+impl Mesh {
+    /// Uploads this mesh to a vertex buffer of [interleaved](Mesh::to_interleaved) vertices, laid
+    /// out as [Vertex::LAYOUT], and an index buffer of `u32`s.
+    ///
+    /// Arguments:
+    /// * `device`: The device to create the buffers on.
+    ///
+    /// Returns:
+    /// The vertex buffer, the index buffer, and the number of indices to draw.
+    pub fn create_buffers(&self, device: &Device) -> (Buffer, Buffer, u32) {
+        let vertices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("trianglyph vertices"),
+            contents: bytemuck::cast_slice(&self.to_interleaved()),
+            usage: BufferUsages::VERTEX,
+        });
+        let indices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("trianglyph indices"),
+            contents: bytemuck::cast_slice(&self.indices),
+            usage: BufferUsages::INDEX,
+        });
+        (vertices, indices, self.indices.len() as u32)
+    }
+}